@@ -45,9 +45,16 @@ fn main() -> ! {
     let mut perclock = perclock.enable(&mut handle);
     let mut gpt = hal::ral::gpt::GPT2::take().unwrap();
     perclock.clock_gate_gpt(&mut gpt, hal::ccm::ClockGate::On);
-    let mut timer = hal::GPT::new(gpt, &perclock);
-
-    timer.set_wait_mode_enable(true);
+    let timer = hal::GPT::new(
+        gpt,
+        &perclock,
+        hal::ClockConfig::default(),
+        hal::RunConfig {
+            wait: true,
+            ..Default::default()
+        },
+    );
+    let mut timer = hal::gpt::Delay::new(timer);
 
     let mut uart_clock = uart_clock.enable(&mut handle);
     let mut uart = hal::ral::lpuart::LPUART2::take().unwrap();
@@ -100,7 +100,7 @@ pub enum ClockGate {
 }
 
 /// Crystal oscillator frequency
-const OSCILLATOR_FREQUENCY_HZ: u32 = 24_000_000;
+pub(crate) const OSCILLATOR_FREQUENCY_HZ: u32 = 24_000_000;
 
 /// A disabled clock of type `Clock`
 ///
@@ -1,11 +1,182 @@
-use crate::iomuxc::{consts::Unsigned, gpio::Pin};
+use crate::iomuxc::{consts::Unsigned, gpio::Pin, Config};
 use crate::ral::{self, gpio::RegisterBlock};
 use core::marker::PhantomData;
 
+mod dynamic;
+#[cfg(feature = "embedded-hal-02")]
+mod eh02;
+#[cfg(feature = "embedded-hal-1")]
+mod eh1;
+mod erased;
+#[cfg(feature = "imxrt1060")]
+mod fast;
+mod group;
+mod open_drain;
+mod wait;
+pub use erased::ErasedGpio;
+#[cfg(feature = "imxrt1060")]
+pub use fast::set_fast_port;
+pub use group::PinGroup;
+pub use wait::{on_interrupt, Wait};
+
 /// Indicates that a pin is configured as an input
 pub enum Input {}
 /// Indicates that a pin is configured as an output
 pub enum Output {}
+/// Indicates that a pin is configured as an open-drain output
+///
+/// An open-drain pin either drives low, or releases the line so that something
+/// else can pull it high. The pin always reads the line's level.
+pub enum OpenDrain {}
+/// Indicates that a pin's direction is selected at runtime
+pub enum Dynamic {}
+
+/// The direction of a [`Dynamic`](enum.Dynamic.html) pin
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// The pin is an input
+    Input,
+    /// The pin is an output
+    Output,
+}
+
+/// The event that triggers a GPIO input's interrupt
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Trigger {
+    /// The input is low
+    Low,
+    /// The input is high
+    High,
+    /// The input transitions from low to high
+    RisingEdge,
+    /// The input transitions from high to low
+    FallingEdge,
+    /// The input transitions in either direction
+    EitherEdge,
+}
+
+/// Returns the register block for a GPIO module, starting with '1'
+fn register_block(module: usize) -> *const RegisterBlock {
+    #[allow(unreachable_patterns)]
+    match module {
+        #[cfg(any(feature = "imxrt1010", feature = "imxrt1060"))]
+        1 => ral::gpio::GPIO1,
+        #[cfg(any(feature = "imxrt1010", feature = "imxrt1060"))]
+        2 => ral::gpio::GPIO2,
+        #[cfg(any(feature = "imxrt1060"))]
+        3 => ral::gpio::GPIO3,
+        #[cfg(any(feature = "imxrt1060"))]
+        4 => ral::gpio::GPIO4,
+        #[cfg(any(feature = "imxrt1010", feature = "imxrt1060"))]
+        5 => ral::gpio::GPIO5,
+        #[cfg(any(feature = "imxrt1060"))]
+        6 => ral::gpio::GPIO6,
+        #[cfg(any(feature = "imxrt1060"))]
+        7 => ral::gpio::GPIO7,
+        #[cfg(any(feature = "imxrt1060"))]
+        8 => ral::gpio::GPIO8,
+        #[cfg(any(feature = "imxrt1060"))]
+        9 => ral::gpio::GPIO9,
+        _ => unreachable!(),
+    }
+}
+
+/// Returns the GPIO module that controls `module`'s pin at `offset`
+///
+/// This differs from `module` when the pin uses a fast bank.
+#[inline(always)]
+fn bank(module: usize, offset: u32) -> usize {
+    #[cfg(feature = "imxrt1060")]
+    {
+        fast::bank(module, offset)
+    }
+    #[cfg(not(feature = "imxrt1060"))]
+    {
+        let _ = offset;
+        module
+    }
+}
+
+/// Set or clear the GDIR bit of `module`'s pin at `offset`
+fn set_gdir(module: usize, offset: u32, output: bool) {
+    // Safety: critical section ensures consistency
+    cortex_m::interrupt::free(|_| unsafe {
+        ral::modify_reg!(
+            ral::gpio,
+            register_block(bank(module, offset)),
+            GDIR,
+            |gdir| if output {
+                gdir | offset
+            } else {
+                gdir & !offset
+            }
+        );
+    });
+}
+
+/// Returns `true` if `module`'s pin at `offset` is an output
+#[inline(always)]
+fn is_output(module: usize, offset: u32) -> bool {
+    // Safety: atomic read
+    unsafe { ral::read_reg!(ral::gpio, register_block(bank(module, offset)), GDIR) & offset != 0 }
+}
+
+/// Drive `module`'s output at `offset` high
+#[inline(always)]
+fn set(module: usize, offset: u32) {
+    // Safety: atomic write
+    unsafe {
+        ral::write_reg!(
+            ral::gpio,
+            register_block(bank(module, offset)),
+            DR_SET,
+            offset
+        )
+    };
+}
+
+/// Drive `module`'s output at `offset` low
+#[inline(always)]
+fn clear(module: usize, offset: u32) {
+    // Safety: atomic write
+    unsafe {
+        ral::write_reg!(
+            ral::gpio,
+            register_block(bank(module, offset)),
+            DR_CLEAR,
+            offset
+        )
+    };
+}
+
+/// Alternate the state of `module`'s output at `offset`
+#[inline(always)]
+fn toggle(module: usize, offset: u32) {
+    // Safety: atomic write
+    unsafe {
+        ral::write_reg!(
+            ral::gpio,
+            register_block(bank(module, offset)),
+            DR_TOGGLE,
+            offset
+        )
+    };
+}
+
+/// Returns `true` if `module`'s output at `offset` is driving high
+#[inline(always)]
+fn is_driven_high(module: usize, offset: u32) -> bool {
+    // Safety: atomic read
+    unsafe { ral::read_reg!(ral::gpio, register_block(bank(module, offset)), DR) & offset != 0 }
+}
+
+/// Returns `true` if `module`'s pad at `offset` is high
+#[inline(always)]
+fn is_high(module: usize, offset: u32) -> bool {
+    // Safety: atomic read
+    unsafe { ral::read_reg!(ral::gpio, register_block(bank(module, offset)), PSR) & offset != 0 }
+}
+
 pub struct GPIO<P, D> {
     pin: P,
     dir: PhantomData<D>,
@@ -16,20 +187,15 @@ where
     P: Pin,
 {
     fn register_block(&self) -> *const RegisterBlock {
-        #[allow(unreachable_patterns)]
-        match self.module() {
-            #[cfg(any(feature = "imxrt1010", feature = "imxrt1060"))]
-            1 => ral::gpio::GPIO1,
-            #[cfg(any(feature = "imxrt1010", feature = "imxrt1060"))]
-            2 => ral::gpio::GPIO2,
-            #[cfg(any(feature = "imxrt1060"))]
-            3 => ral::gpio::GPIO3,
-            #[cfg(any(feature = "imxrt1060"))]
-            4 => ral::gpio::GPIO4,
-            #[cfg(any(feature = "imxrt1010", feature = "imxrt1060"))]
-            5 => ral::gpio::GPIO5,
-            _ => unreachable!(),
-        }
+        register_block(self.bank())
+    }
+
+    /// Returns the GPIO module that controls this pin
+    ///
+    /// This differs from [`module`](#method.module) when the pin uses a fast bank.
+    #[inline(always)]
+    fn bank(&self) -> usize {
+        bank(self.module(), self.offset())
     }
 
     #[inline(always)]
@@ -37,6 +203,26 @@ where
         1u32 << <P as Pin>::Offset::USIZE
     }
 
+    /// Release the pad
+    ///
+    /// The pad keeps its GPIO configuration, including its direction.
+    pub fn release(self) -> P {
+        self.pin
+    }
+
+    /// Set or clear this pin's bit in GDIR
+    fn set_gdir(&self, output: bool) {
+        set_gdir(self.module(), self.offset(), output);
+    }
+
+    /// Apply an electrical configuration to the GPIO's pad
+    ///
+    /// Settings that aren't in `config` are unchanged. See
+    /// [`with_config`](struct.GPIO.html#method.with_config) for an example.
+    pub fn configure(&mut self, config: Config) {
+        crate::iomuxc::configure(&mut self.pin, config);
+    }
+
     /// The return is a non-zero number, since the GPIO identifiers
     /// start with '1.'
     #[inline(always)]
@@ -68,13 +254,34 @@ where
         }
     }
 
+    /// Create a GPIO from a pad, and apply the pad's electrical configuration
+    ///
+    /// `config` selects the pad's pull-up, pull-down, or keeper, its drive strength,
+    /// slew rate, speed, open-drain, and hysteresis. Settings that aren't in `config`
+    /// are unchanged.
+    ///
+    /// ```no_run
+    /// use imxrt_async_hal as hal;
+    /// use hal::{gpio::GPIO, iomuxc};
+    ///
+    /// const BUTTON: iomuxc::Config = iomuxc::Config::zero()
+    ///     .set_pull_keep(iomuxc::PullKeep::Enabled)
+    ///     .set_pull_keep_select(iomuxc::PullKeepSelect::Pull)
+    ///     .set_pull_up_down(iomuxc::PullUpDown::Pullup22k)
+    ///     .set_hysteresis(iomuxc::Hysteresis::Enabled);
+    ///
+    /// let pads = hal::iomuxc::new(hal::ral::iomuxc::IOMUXC::take().unwrap());
+    /// let button = GPIO::with_config(pads.b0.p03, BUTTON);
+    /// ```
+    pub fn with_config(pin: P, config: Config) -> Self {
+        let mut gpio = Self::new(pin);
+        gpio.configure(config);
+        gpio
+    }
+
     /// Transition the GPIO from an input to an output
     pub fn output(self) -> GPIO<P, Output> {
-        // Safety: critical section ensures consistency
-        cortex_m::interrupt::free(|_| unsafe {
-            ral::modify_reg!(ral::gpio, self.register_block(), GDIR, |gdir| gdir
-                | self.offset());
-        });
+        self.set_gdir(true);
         GPIO {
             pin: self.pin,
             dir: PhantomData,
@@ -83,8 +290,109 @@ where
 
     /// Returns `true` if this input pin is high
     pub fn is_set(&self) -> bool {
-        // Safety: read is atomic
-        unsafe { ral::read_reg!(ral::gpio, self.register_block(), PSR) & self.offset() != 0 }
+        is_high(self.module(), self.offset())
+    }
+
+    /// Select the event that triggers this input's interrupt
+    ///
+    /// Changing the trigger may flag the interrupt status. This clears the status
+    /// before the change can signal an interrupt, so the next status reflects the
+    /// new trigger.
+    pub fn set_interrupt_trigger(&mut self, trigger: Trigger) {
+        use ral::gpio::ICR1::ICR0::RW::{FALLING_EDGE, HIGH_LEVEL, LOW_LEVEL, RISING_EDGE};
+
+        let icr = match trigger {
+            Trigger::Low => LOW_LEVEL,
+            Trigger::High => HIGH_LEVEL,
+            Trigger::RisingEdge => RISING_EDGE,
+            Trigger::FallingEdge => FALLING_EDGE,
+            // EDGE_SEL overrides ICR
+            Trigger::EitherEdge => LOW_LEVEL,
+        };
+        // Each ICR register configures 16 pins, two bits per pin
+        let index = <P as Pin>::Offset::USIZE;
+        let shift = (index % 16) * 2;
+        let update = |reg: u32| (reg & !(0b11 << shift)) | (icr << shift);
+        // Safety: critical section ensures consistency with other pins in this port
+        cortex_m::interrupt::free(|_| unsafe {
+            if index < 16 {
+                ral::modify_reg!(ral::gpio, self.register_block(), ICR1, update);
+            } else {
+                ral::modify_reg!(ral::gpio, self.register_block(), ICR2, update);
+            }
+            ral::modify_reg!(ral::gpio, self.register_block(), EDGE_SEL, |edge_sel| {
+                if trigger == Trigger::EitherEdge {
+                    edge_sel | self.offset()
+                } else {
+                    edge_sel & !self.offset()
+                }
+            });
+            ral::write_reg!(ral::gpio, self.register_block(), ISR, self.offset());
+        });
+    }
+
+    /// Returns the event that triggers this input's interrupt
+    pub fn interrupt_trigger(&self) -> Trigger {
+        use ral::gpio::ICR1::ICR0::RW::{FALLING_EDGE, HIGH_LEVEL, LOW_LEVEL, RISING_EDGE};
+
+        // Safety: atomic reads
+        let (edge_sel, icr) = unsafe {
+            let edge_sel = ral::read_reg!(ral::gpio, self.register_block(), EDGE_SEL);
+            let index = <P as Pin>::Offset::USIZE;
+            let icr = if index < 16 {
+                ral::read_reg!(ral::gpio, self.register_block(), ICR1)
+            } else {
+                ral::read_reg!(ral::gpio, self.register_block(), ICR2)
+            };
+            (edge_sel, (icr >> ((index % 16) * 2)) & 0b11)
+        };
+        if edge_sel & self.offset() != 0 {
+            return Trigger::EitherEdge;
+        }
+        match icr {
+            LOW_LEVEL => Trigger::Low,
+            HIGH_LEVEL => Trigger::High,
+            RISING_EDGE => Trigger::RisingEdge,
+            FALLING_EDGE => Trigger::FallingEdge,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Enable or disable this input's interrupt
+    ///
+    /// When enabled, the trigger event signals the GPIO port's interrupt.
+    pub fn set_interrupt_enable(&mut self, enable: bool) {
+        // Safety: critical section ensures consistency with other pins in this port
+        cortex_m::interrupt::free(|_| unsafe {
+            ral::modify_reg!(ral::gpio, self.register_block(), IMR, |imr| if enable {
+                imr | self.offset()
+            } else {
+                imr & !self.offset()
+            });
+        });
+    }
+
+    /// Indicates if this input's interrupt is enabled
+    pub fn interrupt_enabled(&self) -> bool {
+        // Safety: atomic read
+        unsafe { ral::read_reg!(ral::gpio, self.register_block(), IMR) & self.offset() != 0 }
+    }
+
+    /// Returns `true` if the trigger event has happened
+    ///
+    /// The status is set whether or not the interrupt is enabled.
+    pub fn interrupt_status(&self) -> bool {
+        // Safety: atomic read
+        unsafe { ral::read_reg!(ral::gpio, self.register_block(), ISR) & self.offset() != 0 }
+    }
+
+    /// Clear the interrupt status flag
+    ///
+    /// It's necessary to clear the flag when the trigger event signals an
+    /// interrupt. Level triggers set the flag again while the level holds.
+    pub fn clear_interrupt_status(&mut self) {
+        // Safety: ISR is W1C, so this atomic write only affects this pin
+        unsafe { ral::write_reg!(ral::gpio, self.register_block(), ISR, self.offset()) };
     }
 }
 
@@ -94,11 +402,7 @@ where
 {
     /// Transition the pin from an output to an input
     pub fn input(self) -> GPIO<P, Input> {
-        // Safety: critical section ensures consistency
-        cortex_m::interrupt::free(|_| unsafe {
-            ral::modify_reg!(ral::gpio, self.register_block(), GDIR, |gdir| gdir
-                & !self.offset());
-        });
+        self.set_gdir(false);
         GPIO {
             pin: self.pin,
             dir: PhantomData,
@@ -107,20 +411,17 @@ where
 
     /// Drive the GPIO high
     pub fn set(&mut self) {
-        // Safety: atomic write
-        unsafe { ral::write_reg!(ral::gpio, self.register_block(), DR_SET, self.offset()) };
+        set(self.module(), self.offset());
     }
 
     /// Drive the GPIO low
     pub fn clear(&mut self) {
-        // Safety: atomic write
-        unsafe { ral::write_reg!(ral::gpio, self.register_block(), DR_CLEAR, self.offset()) };
+        clear(self.module(), self.offset());
     }
 
     /// Returns `true` if the pin is driving high
     pub fn is_set(&self) -> bool {
-        // Safety: atomic read
-        unsafe { ral::read_reg!(ral::gpio, self.register_block(), DR) & self.offset() != 0u32 }
+        is_driven_high(self.module(), self.offset())
     }
 
     /// Alternate the state of the pin
@@ -128,7 +429,6 @@ where
     /// Using `toggle` will be more efficient than checking [`is_set`](#method.is_set)
     /// and then selecting the opposite state.
     pub fn toggle(&mut self) {
-        // Safety: atomic write
-        unsafe { ral::write_reg!(ral::gpio, self.register_block(), DR_TOGGLE, self.offset()) }
+        toggle(self.module(), self.offset());
     }
 }
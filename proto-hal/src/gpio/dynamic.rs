@@ -0,0 +1,98 @@
+//! GPIOs with a runtime direction
+
+use super::{Direction, Dynamic, Input, Output, GPIO};
+use crate::iomuxc::gpio::Pin;
+use core::marker::PhantomData;
+
+impl<P> GPIO<P, Input>
+where
+    P: Pin,
+{
+    /// Select the GPIO's direction at runtime
+    ///
+    /// The pin remains an input until you change its direction.
+    pub fn dynamic(self) -> GPIO<P, Dynamic> {
+        GPIO {
+            pin: self.pin,
+            dir: PhantomData,
+        }
+    }
+}
+
+impl<P> GPIO<P, Output>
+where
+    P: Pin,
+{
+    /// Select the GPIO's direction at runtime
+    ///
+    /// The pin remains an output until you change its direction.
+    pub fn dynamic(self) -> GPIO<P, Dynamic> {
+        GPIO {
+            pin: self.pin,
+            dir: PhantomData,
+        }
+    }
+}
+
+impl<P> GPIO<P, Dynamic>
+where
+    P: Pin,
+{
+    /// Returns the pin's direction
+    pub fn direction(&self) -> Direction {
+        if super::is_output(self.module(), self.offset()) {
+            Direction::Output
+        } else {
+            Direction::Input
+        }
+    }
+
+    /// Change the pin's direction
+    pub fn set_direction(&mut self, direction: Direction) {
+        self.set_gdir(direction == Direction::Output);
+    }
+
+    /// Drive the GPIO high
+    ///
+    /// If the pin is an input, it drives high once it becomes an output.
+    pub fn set(&mut self) {
+        super::set(self.module(), self.offset());
+    }
+
+    /// Drive the GPIO low
+    ///
+    /// If the pin is an input, it drives low once it becomes an output.
+    pub fn clear(&mut self) {
+        super::clear(self.module(), self.offset());
+    }
+
+    /// Alternate the state that the pin drives
+    pub fn toggle(&mut self) {
+        super::toggle(self.module(), self.offset());
+    }
+
+    /// Returns `true` if the pad is high
+    ///
+    /// This reads the pad in either direction.
+    pub fn is_set(&self) -> bool {
+        super::is_high(self.module(), self.offset())
+    }
+
+    /// Transition the pin to an input
+    pub fn input(self) -> GPIO<P, Input> {
+        self.set_gdir(false);
+        GPIO {
+            pin: self.pin,
+            dir: PhantomData,
+        }
+    }
+
+    /// Transition the pin to an output
+    pub fn output(self) -> GPIO<P, Output> {
+        self.set_gdir(true);
+        GPIO {
+            pin: self.pin,
+            dir: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! embedded-hal 0.2 digital traits
+
+use super::{Input, Output, GPIO};
+use crate::iomuxc::gpio::Pin;
+use core::convert::Infallible;
+use embedded_hal_02::digital::v2::{InputPin, OutputPin, StatefulOutputPin, ToggleableOutputPin};
+
+impl<P> OutputPin for GPIO<P, Output>
+where
+    P: Pin,
+{
+    type Error = Infallible;
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.set();
+        Ok(())
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.clear();
+        Ok(())
+    }
+}
+
+impl<P> StatefulOutputPin for GPIO<P, Output>
+where
+    P: Pin,
+{
+    fn is_set_high(&self) -> Result<bool, Self::Error> {
+        Ok(self.is_set())
+    }
+
+    fn is_set_low(&self) -> Result<bool, Self::Error> {
+        Ok(!self.is_set())
+    }
+}
+
+impl<P> ToggleableOutputPin for GPIO<P, Output>
+where
+    P: Pin,
+{
+    type Error = Infallible;
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        GPIO::<P, Output>::toggle(self);
+        Ok(())
+    }
+}
+
+impl<P> InputPin for GPIO<P, Input>
+where
+    P: Pin,
+{
+    type Error = Infallible;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        Ok(self.is_set())
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        Ok(!self.is_set())
+    }
+}
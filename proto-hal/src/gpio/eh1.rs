@@ -0,0 +1,59 @@
+//! embedded-hal 1.0 digital traits
+
+use super::{Input, Output, GPIO};
+use crate::iomuxc::gpio::Pin;
+use core::convert::Infallible;
+use embedded_hal_1::digital::{ErrorType, InputPin, OutputPin, StatefulOutputPin};
+
+impl<P, D> ErrorType for GPIO<P, D>
+where
+    P: Pin,
+{
+    type Error = Infallible;
+}
+
+impl<P> OutputPin for GPIO<P, Output>
+where
+    P: Pin,
+{
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.set();
+        Ok(())
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.clear();
+        Ok(())
+    }
+}
+
+impl<P> StatefulOutputPin for GPIO<P, Output>
+where
+    P: Pin,
+{
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.is_set())
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_set())
+    }
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        GPIO::<P, Output>::toggle(self);
+        Ok(())
+    }
+}
+
+impl<P> InputPin for GPIO<P, Input>
+where
+    P: Pin,
+{
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.is_set())
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_set())
+    }
+}
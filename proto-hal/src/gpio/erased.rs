@@ -0,0 +1,96 @@
+//! GPIOs without their pad type
+
+use super::{Input, Output, GPIO};
+use crate::iomuxc::gpio::Pin;
+use core::marker::PhantomData;
+
+/// A GPIO that doesn't know its pad type
+///
+/// Unlike a `GPIO<P, D>`, GPIOs on different pads have the same `ErasedGpio<D>`
+/// type. Keep them in an array, or select one at runtime. Use
+/// [`GPIO::erase`](struct.GPIO.html#method.erase) to create an `ErasedGpio`.
+///
+/// On the i.MX RT 1060, an `ErasedGpio` follows its pin when the pin moves to a
+/// different bank.
+///
+/// ```no_run
+/// use imxrt_async_hal as hal;
+/// use hal::gpio::GPIO;
+///
+/// let pads = hal::iomuxc::new(hal::ral::iomuxc::IOMUXC::take().unwrap());
+/// let mut leds = [
+///     GPIO::new(pads.b0.p03).output().erase(),
+///     GPIO::new(pads.ad_b0.p09).output().erase(),
+/// ];
+/// for led in leds.iter_mut() {
+///     led.set();
+/// }
+/// ```
+pub struct ErasedGpio<D> {
+    module: usize,
+    offset: u32,
+    dir: PhantomData<D>,
+}
+
+impl<P, D> GPIO<P, D>
+where
+    P: Pin,
+{
+    /// Erase the pad type from the GPIO
+    pub fn erase(self) -> ErasedGpio<D> {
+        ErasedGpio {
+            module: self.module(),
+            offset: self.offset(),
+            dir: PhantomData,
+        }
+    }
+}
+
+impl ErasedGpio<Input> {
+    /// Transition the GPIO from an input to an output
+    pub fn output(self) -> ErasedGpio<Output> {
+        super::set_gdir(self.module, self.offset, true);
+        ErasedGpio {
+            module: self.module,
+            offset: self.offset,
+            dir: PhantomData,
+        }
+    }
+
+    /// Returns `true` if this input pin is high
+    pub fn is_set(&self) -> bool {
+        super::is_high(self.module, self.offset)
+    }
+}
+
+impl ErasedGpio<Output> {
+    /// Transition the pin from an output to an input
+    pub fn input(self) -> ErasedGpio<Input> {
+        super::set_gdir(self.module, self.offset, false);
+        ErasedGpio {
+            module: self.module,
+            offset: self.offset,
+            dir: PhantomData,
+        }
+    }
+
+    /// Drive the GPIO high
+    pub fn set(&mut self) {
+        super::set(self.module, self.offset);
+    }
+
+    /// Drive the GPIO low
+    pub fn clear(&mut self) {
+        super::clear(self.module, self.offset);
+    }
+
+    /// Returns `true` if the pin is driving high
+    pub fn is_set(&self) -> bool {
+        super::is_driven_high(self.module, self.offset)
+    }
+
+    /// Alternate the state of the pin
+    pub fn toggle(&mut self) {
+        super::toggle(self.module, self.offset);
+    }
+}
@@ -0,0 +1,157 @@
+//! Fast GPIO banks on the i.MX RT 1060
+//!
+//! Each pin of GPIO1 through GPIO4 can be routed to a tightly-coupled bank,
+//! GPIO6 through GPIO9. The CPU accesses the fast banks in a single cycle,
+//! which helps bit-banged protocols. IOMUXC_GPR26 through GPR29 select the bank
+//! for each pin.
+//!
+//! When a pin moves to a bank, its direction, output value and interrupt
+//! configuration move with it. So does an input's pending `Wait`. An interrupt
+//! that the old bank flagged, but that the application hasn't handled, is lost
+//! in the move. The fast banks share the `GPIO6_7_8_9` interrupt; call
+//! [`on_interrupt`](fn.on_interrupt.html) for each of GPIO6 through GPIO9 from
+//! that handler.
+//!
+//! The HAL reads GPR26 through GPR29 on every access, so it follows writes that
+//! don't go through these functions, like `ral::iomuxc_gpr::reset_all`. Those
+//! writes don't move a pin's state to its new bank, though. Prefer these
+//! functions to move pins.
+
+use super::{register_block, GPIO};
+use crate::iomuxc::gpio::Pin;
+use crate::ral;
+
+/// The fast banks are these many modules after their standard bank
+const FAST_OFFSET: usize = 5;
+
+/// Returns the pins of `module` that use the fast bank
+pub(super) fn fast_pins(module: usize) -> u32 {
+    match module {
+        // Safety: atomic read
+        1..=4 => unsafe { read_gpr(module) },
+        _ => 0,
+    }
+}
+
+/// Returns the GPIO module that controls the pins of `module` in `mask`
+pub(super) fn bank(module: usize, mask: u32) -> usize {
+    if fast_pins(module) & mask != 0 {
+        module + FAST_OFFSET
+    } else {
+        module
+    }
+}
+
+unsafe fn read_gpr(module: usize) -> u32 {
+    match module {
+        1 => ral::read_reg!(ral::iomuxc_gpr, ral::iomuxc_gpr::IOMUXC_GPR, GPR26),
+        2 => ral::read_reg!(ral::iomuxc_gpr, ral::iomuxc_gpr::IOMUXC_GPR, GPR27),
+        3 => ral::read_reg!(ral::iomuxc_gpr, ral::iomuxc_gpr::IOMUXC_GPR, GPR28),
+        4 => ral::read_reg!(ral::iomuxc_gpr, ral::iomuxc_gpr::IOMUXC_GPR, GPR29),
+        _ => unreachable!(),
+    }
+}
+
+unsafe fn write_gpr(module: usize, gpr: u32) {
+    match module {
+        1 => ral::write_reg!(ral::iomuxc_gpr, ral::iomuxc_gpr::IOMUXC_GPR, GPR26, gpr),
+        2 => ral::write_reg!(ral::iomuxc_gpr, ral::iomuxc_gpr::IOMUXC_GPR, GPR27, gpr),
+        3 => ral::write_reg!(ral::iomuxc_gpr, ral::iomuxc_gpr::IOMUXC_GPR, GPR28, gpr),
+        4 => ral::write_reg!(ral::iomuxc_gpr, ral::iomuxc_gpr::IOMUXC_GPR, GPR29, gpr),
+        _ => unreachable!(),
+    }
+}
+
+/// Widen each of the low 16 bits of `pins` into a two-bit ICR field
+///
+/// ICR1 holds the fields of pins 0 through 15, and ICR2 holds the fields of
+/// pins 16 through 31.
+fn icr_fields(pins: u32) -> u32 {
+    (0..16)
+        .filter(|pin| pins & (1 << pin) != 0)
+        .fold(0, |fields, pin| fields | (0b11 << (pin * 2)))
+}
+
+/// Move `module`'s pins in `mask` to the fast bank (`true`) or the standard bank (`false`)
+///
+/// Pins that already use the requested bank are untouched.
+pub(super) fn route(module: usize, mask: u32, fast: bool) {
+    assert!(
+        (1..=4).contains(&module),
+        "Only GPIO1 through GPIO4 have fast banks"
+    );
+    let (from, to) = if fast {
+        (module, module + FAST_OFFSET)
+    } else {
+        (module + FAST_OFFSET, module)
+    };
+    let (from_block, to_block) = (register_block(from), register_block(to));
+    // Safety: critical section ensures consistency with other pins in this port
+    cortex_m::interrupt::free(|cs| unsafe {
+        let gpr = read_gpr(module);
+        let moving = if fast { mask & !gpr } else { mask & gpr };
+        if moving == 0 {
+            return;
+        }
+
+        // Mask the moving pins on the old bank before they change hands
+        let imr = ral::read_reg!(ral::gpio, from_block, IMR);
+        ral::modify_reg!(ral::gpio, from_block, IMR, |from_imr| from_imr & !moving);
+        ral::write_reg!(ral::gpio, from_block, ISR, moving);
+
+        macro_rules! migrate {
+            ($reg:ident, $mask:expr) => {{
+                let mask = $mask;
+                let value = ral::read_reg!(ral::gpio, from_block, $reg);
+                ral::modify_reg!(ral::gpio, to_block, $reg, |to| (to & !mask)
+                    | (value & mask));
+            }};
+        }
+        migrate!(DR, moving);
+        migrate!(GDIR, moving);
+        migrate!(ICR1, icr_fields(moving));
+        migrate!(ICR2, icr_fields(moving >> 16));
+        migrate!(EDGE_SEL, moving);
+
+        let gpr = gpr ^ moving;
+        write_gpr(module, gpr);
+
+        // The pins now sample on the new bank. Clear anything the new bank
+        // flagged while the pins were elsewhere, then unmask.
+        ral::write_reg!(ral::gpio, to_block, ISR, moving);
+        ral::modify_reg!(ral::gpio, to_block, IMR, |to_imr| (to_imr & !moving)
+            | (imr & moving));
+        super::wait::move_wakers(from, to, moving, cs);
+    });
+}
+
+/// Move every pin of a GPIO port to its fast bank (`true`) or its standard bank (`false`)
+///
+/// `module` is the GPIO identifier of the standard bank, 1 through 4. This affects
+/// every pin in the port, including pins that are used by other `GPIO`s.
+///
+/// # Panics
+///
+/// Panics if `module` isn't 1 through 4.
+pub fn set_fast_port(module: usize, fast: bool) {
+    route(module, u32::MAX, fast);
+}
+
+impl<P, D> GPIO<P, D>
+where
+    P: Pin,
+{
+    /// Move the pin to its fast bank (`true`) or its standard bank (`false`)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pin belongs to GPIO5, which has no fast bank.
+    pub fn set_fast(&mut self, fast: bool) {
+        route(self.module(), self.offset(), fast);
+    }
+
+    /// Returns `true` if the pin uses its fast bank
+    pub fn is_fast(&self) -> bool {
+        fast_pins(self.module()) & self.offset() != 0
+    }
+}
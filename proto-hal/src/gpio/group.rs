@@ -0,0 +1,178 @@
+//! Groups of GPIO outputs on the same port
+
+use super::{register_block, Output, GPIO};
+use crate::iomuxc::{consts::Unsigned, gpio::Pin};
+use crate::ral::{self, gpio::RegisterBlock};
+use core::marker::PhantomData;
+
+/// GPIO outputs that change together
+///
+/// All of the outputs belong to the same GPIO module, `M`, so the group changes
+/// every output with one register write. Use a group for bit-parallel buses, like
+/// LCD data lines.
+///
+/// On the i.MX RT 1060, all of the outputs must use the same bank. Use
+/// [`set_fast`](#method.set_fast) to move them together.
+///
+/// The bits of a group's values are the bits of the GPIO port. Bit `n` is the pin
+/// at offset `n`. The group ignores the bits of pins that aren't in the group.
+///
+/// ```no_run
+/// use imxrt_async_hal as hal;
+/// use hal::gpio::{GPIO, PinGroup};
+///
+/// let pads = hal::iomuxc::new(hal::ral::iomuxc::IOMUXC::take().unwrap());
+/// let d0 = GPIO::new(pads.b0.p00).output();
+/// let d1 = GPIO::new(pads.b0.p01).output();
+///
+/// let mut bus = PinGroup::new(d0).with(d1);
+/// bus.write(0b10);
+/// let (d0, d1) = bus.release();
+/// ```
+pub struct PinGroup<M, T> {
+    pins: T,
+    mask: u32,
+    module: PhantomData<M>,
+}
+
+impl<M, P> PinGroup<M, GPIO<P, Output>>
+where
+    M: Unsigned,
+    P: Pin<Module = M>,
+{
+    /// Start a group with one output
+    pub fn new(gpio: GPIO<P, Output>) -> Self {
+        PinGroup {
+            mask: gpio.offset(),
+            pins: gpio,
+            module: PhantomData,
+        }
+    }
+}
+
+impl<M, T> PinGroup<M, T>
+where
+    M: Unsigned,
+{
+    /// Add another output to the group
+    ///
+    /// The output must belong to the same GPIO module as the rest of the group.
+    /// Otherwise, this doesn't compile.
+    ///
+    /// # Panics
+    ///
+    /// On the i.MX RT 1060, panics if the output doesn't use the same bank as the
+    /// rest of the group.
+    pub fn with<P>(self, gpio: GPIO<P, Output>) -> PinGroup<M, (T, GPIO<P, Output>)>
+    where
+        P: Pin<Module = M>,
+    {
+        #[cfg(feature = "imxrt1060")]
+        assert!(
+            same_bank::<M>(self.mask | gpio.offset()),
+            "Pins in a group must use the same bank"
+        );
+        PinGroup {
+            mask: self.mask | gpio.offset(),
+            pins: (self.pins, gpio),
+            module: PhantomData,
+        }
+    }
+
+    /// Release the outputs
+    ///
+    /// The outputs are returned in the order they were added to the group.
+    pub fn release(self) -> T {
+        self.pins
+    }
+
+    /// Returns the port bits of the outputs in this group
+    pub fn mask(&self) -> u32 {
+        self.mask
+    }
+
+    fn register_block(&self) -> *const RegisterBlock {
+        #[cfg(feature = "imxrt1060")]
+        {
+            assert!(
+                same_bank::<M>(self.mask),
+                "Pins in a group must use the same bank"
+            );
+            register_block(super::fast::bank(M::USIZE, self.mask))
+        }
+        #[cfg(not(feature = "imxrt1060"))]
+        {
+            register_block(M::USIZE)
+        }
+    }
+
+    /// Move every output in the group to its fast bank (`true`) or its standard
+    /// bank (`false`)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the group belongs to GPIO5, which has no fast bank.
+    #[cfg(feature = "imxrt1060")]
+    pub fn set_fast(&mut self, fast: bool) {
+        super::fast::route(M::USIZE, self.mask, fast);
+    }
+
+    /// Drive every output in the group to the value of its bit
+    ///
+    /// All outputs change with one write to the data register.
+    pub fn write(&mut self, value: u32) {
+        let mask = self.mask;
+        // Safety: critical section ensures consistency with other pins in this port
+        cortex_m::interrupt::free(|_| unsafe {
+            ral::modify_reg!(ral::gpio, self.register_block(), DR, |dr| (dr & !mask)
+                | (value & mask));
+        });
+    }
+
+    /// Drive high the outputs whose bits are set in `bits`
+    pub fn set(&mut self, bits: u32) {
+        // Safety: atomic write
+        unsafe { ral::write_reg!(ral::gpio, self.register_block(), DR_SET, bits & self.mask) };
+    }
+
+    /// Drive low the outputs whose bits are set in `bits`
+    pub fn clear(&mut self, bits: u32) {
+        // Safety: atomic write
+        unsafe { ral::write_reg!(ral::gpio, self.register_block(), DR_CLEAR, bits & self.mask) };
+    }
+
+    /// Alternate the state of the outputs whose bits are set in `bits`
+    pub fn toggle(&mut self, bits: u32) {
+        // Safety: atomic write
+        unsafe {
+            ral::write_reg!(
+                ral::gpio,
+                self.register_block(),
+                DR_TOGGLE,
+                bits & self.mask
+            )
+        };
+    }
+
+    /// Returns the values that the outputs are driving
+    pub fn driven(&self) -> u32 {
+        // Safety: atomic read
+        unsafe { ral::read_reg!(ral::gpio, self.register_block(), DR) & self.mask }
+    }
+
+    /// Returns the levels of the pads in this group
+    ///
+    /// The levels are read from the pad status register, so they may differ from
+    /// the driven values if something else is driving the bus.
+    pub fn read(&self) -> u32 {
+        // Safety: atomic read
+        unsafe { ral::read_reg!(ral::gpio, self.register_block(), PSR) & self.mask }
+    }
+}
+
+/// Returns `true` if all of module `M`'s pins in `mask` use the same bank
+#[cfg(feature = "imxrt1060")]
+fn same_bank<M: Unsigned>(mask: u32) -> bool {
+    let fast = super::fast::fast_pins(M::USIZE) & mask;
+    fast == 0 || fast == mask
+}
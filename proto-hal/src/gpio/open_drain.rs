@@ -0,0 +1,65 @@
+//! Open-drain GPIO outputs
+
+use super::{Input, OpenDrain, GPIO};
+use crate::iomuxc::{self, gpio::Pin, Config};
+use core::marker::PhantomData;
+
+const OPEN_DRAIN: Config = Config::zero().set_open_drain(iomuxc::OpenDrain::Enabled);
+const PUSH_PULL: Config = Config::zero().set_open_drain(iomuxc::OpenDrain::Disabled);
+
+impl<P> GPIO<P, Input>
+where
+    P: Pin,
+{
+    /// Transition the GPIO from an input to an open-drain output
+    ///
+    /// This enables the pad's open-drain bit. The pin starts by releasing the line.
+    pub fn open_drain(mut self) -> GPIO<P, OpenDrain> {
+        self.configure(OPEN_DRAIN);
+        super::set(self.module(), self.offset());
+        self.set_gdir(true);
+        GPIO {
+            pin: self.pin,
+            dir: PhantomData,
+        }
+    }
+}
+
+impl<P> GPIO<P, OpenDrain>
+where
+    P: Pin,
+{
+    /// Transition the pin from an open-drain output to an input
+    ///
+    /// This disables the pad's open-drain bit.
+    pub fn input(mut self) -> GPIO<P, Input> {
+        self.set_gdir(false);
+        self.configure(PUSH_PULL);
+        GPIO {
+            pin: self.pin,
+            dir: PhantomData,
+        }
+    }
+
+    /// Release the line, so that something else may pull it high
+    pub fn set(&mut self) {
+        super::set(self.module(), self.offset());
+    }
+
+    /// Drive the line low
+    pub fn clear(&mut self) {
+        super::clear(self.module(), self.offset());
+    }
+
+    /// Returns `true` if the pin is driving the line low
+    pub fn is_driving_low(&self) -> bool {
+        !super::is_driven_high(self.module(), self.offset())
+    }
+
+    /// Returns `true` if the line is high
+    ///
+    /// This reads the pad, so it's `false` when something else drives the line low.
+    pub fn is_set(&self) -> bool {
+        super::is_high(self.module(), self.offset())
+    }
+}
@@ -0,0 +1,189 @@
+//! Async GPIO inputs
+//!
+//! The `wait_for_*` methods on a `GPIO<P, Input>` return a future that resolves
+//! when the input sees its trigger. The futures only need a `Waker`, so they work
+//! with any executor.
+//!
+//! The futures rely on the GPIO module's interrupt. Your application must
+//! unmask the module's interrupts in the NVIC, and call [`on_interrupt`] from
+//! each of the module's interrupt handlers. Otherwise, the futures never resolve.
+//!
+//! [`on_interrupt`]: fn.on_interrupt.html
+
+use super::{register_block, Input, Trigger, GPIO};
+use crate::iomuxc::{consts::Unsigned, gpio::Pin};
+use crate::ral;
+use core::{
+    cell::RefCell,
+    future::Future,
+    task::{Context, Poll, Waker},
+};
+#[cfg(feature = "imxrt1060")]
+use cortex_m::interrupt::CriticalSection;
+use cortex_m::interrupt::{self, Mutex};
+
+/// The number of GPIO modules, GPIO1 through GPIO9
+const MODULES: usize = 9;
+
+/// Holds the waker for one input
+type Slot = Mutex<RefCell<Option<Waker>>>;
+// The constants only initialize WAKERS
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY: Slot = Mutex::new(RefCell::new(None));
+#[allow(clippy::declare_interior_mutable_const)]
+const MODULE: [Slot; 32] = [EMPTY; 32];
+
+/// The waker slot for each input of each GPIO module
+static WAKERS: [[Slot; 32]; MODULES] = [MODULE; MODULES];
+
+/// Wake the futures that are waiting on a GPIO module's inputs
+///
+/// Call this from every interrupt handler for the GPIO module. For GPIO1 on an
+/// i.MX RT 1060, that's `GPIO1_Combined_0_15` and `GPIO1_Combined_16_31`. `module`
+/// is the GPIO identifier, which starts with '1.'
+///
+/// `on_interrupt` masks the interrupt of every input that saw its trigger. The
+/// input's future unmasks it if the future needs to wait again.
+pub fn on_interrupt(module: usize) {
+    let gpio = register_block(module);
+    interrupt::free(|cs| {
+        // Safety: critical section ensures consistency
+        let pending = unsafe {
+            let pending =
+                ral::read_reg!(ral::gpio, gpio, ISR) & ral::read_reg!(ral::gpio, gpio, IMR);
+            ral::modify_reg!(ral::gpio, gpio, IMR, |imr| imr & !pending);
+            pending
+        };
+        WAKERS[module - 1]
+            .iter()
+            .enumerate()
+            .filter(|(offset, _)| pending & (1 << offset) != 0)
+            .filter_map(|(_, slot)| slot.borrow(cs).borrow_mut().take())
+            .for_each(Waker::wake);
+    });
+}
+
+/// Move the wakers of `from`'s inputs in `mask` to the same inputs of `to`
+///
+/// Call this in the critical section that moves the inputs between banks.
+#[cfg(feature = "imxrt1060")]
+pub(super) fn move_wakers(from: usize, to: usize, mask: u32, cs: &CriticalSection) {
+    WAKERS[from - 1]
+        .iter()
+        .zip(WAKERS[to - 1].iter())
+        .enumerate()
+        .filter(|(offset, _)| mask & (1 << offset) != 0)
+        .for_each(|(_, (from, to))| {
+            *to.borrow(cs).borrow_mut() = from.borrow(cs).borrow_mut().take();
+        });
+}
+
+impl<P> GPIO<P, Input>
+where
+    P: Pin,
+{
+    /// Wait for the input to be high
+    ///
+    /// The future resolves immediately if the input is already high.
+    pub fn wait_for_high(&mut self) -> Wait<'_, P> {
+        Wait::new(self, Trigger::High)
+    }
+
+    /// Wait for the input to be low
+    ///
+    /// The future resolves immediately if the input is already low.
+    pub fn wait_for_low(&mut self) -> Wait<'_, P> {
+        Wait::new(self, Trigger::Low)
+    }
+
+    /// Wait for the input to transition from low to high
+    pub fn wait_for_rising_edge(&mut self) -> Wait<'_, P> {
+        Wait::new(self, Trigger::RisingEdge)
+    }
+
+    /// Wait for the input to transition from high to low
+    pub fn wait_for_falling_edge(&mut self) -> Wait<'_, P> {
+        Wait::new(self, Trigger::FallingEdge)
+    }
+
+    /// Wait for the input to transition in either direction
+    pub fn wait_for_any_edge(&mut self) -> Wait<'_, P> {
+        Wait::new(self, Trigger::EitherEdge)
+    }
+}
+
+/// A future that resolves when a GPIO input sees its trigger
+///
+/// Use the `wait_for_*` methods on a `GPIO<P, Input>` to create a `Wait`. The
+/// input's interrupt trigger remains configured after the future resolves.
+pub struct Wait<'a, P>
+where
+    P: Pin,
+{
+    gpio: &'a mut GPIO<P, Input>,
+    trigger: Trigger,
+    armed: bool,
+}
+
+impl<'a, P> Wait<'a, P>
+where
+    P: Pin,
+{
+    fn new(gpio: &'a mut GPIO<P, Input>, trigger: Trigger) -> Self {
+        Wait {
+            gpio,
+            trigger,
+            armed: false,
+        }
+    }
+
+    fn slot(&self) -> &'static Slot {
+        &WAKERS[self.gpio.bank() - 1][<P as Pin>::Offset::USIZE]
+    }
+}
+
+impl<'a, P> Future for Wait<'a, P>
+where
+    P: Pin,
+{
+    type Output = ();
+
+    fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = &mut *self;
+        if this.armed {
+            if this.gpio.interrupt_status() {
+                this.gpio.set_interrupt_enable(false);
+                this.gpio.clear_interrupt_status();
+                this.armed = false;
+                return Poll::Ready(());
+            }
+        } else {
+            match this.trigger {
+                Trigger::High if this.gpio.is_set() => return Poll::Ready(()),
+                Trigger::Low if !this.gpio.is_set() => return Poll::Ready(()),
+                _ => {}
+            }
+            this.gpio.set_interrupt_trigger(this.trigger);
+            this.armed = true;
+        }
+        let slot = this.slot();
+        interrupt::free(|cs| *slot.borrow(cs).borrow_mut() = Some(cx.waker().clone()));
+        // If the trigger happened since we checked the status, the interrupt fires
+        // as soon as it's unmasked.
+        this.gpio.set_interrupt_enable(true);
+        Poll::Pending
+    }
+}
+
+impl<'a, P> Drop for Wait<'a, P>
+where
+    P: Pin,
+{
+    fn drop(&mut self) {
+        if self.armed {
+            self.gpio.set_interrupt_enable(false);
+            let slot = self.slot();
+            interrupt::free(|cs| slot.borrow(cs).borrow_mut().take());
+        }
+    }
+}
@@ -1,26 +1,137 @@
 use crate::ral;
 use core::time::Duration;
 
+mod async_timer;
+mod capture;
+mod compare;
+mod delay;
+#[cfg(feature = "embassy")]
+pub mod embassy;
+#[cfg(feature = "imxrt1060")]
+mod frequency;
+mod monotonic;
+#[cfg(feature = "imxrt1060")]
+mod pins;
+mod timers;
+pub use async_timer::{AsyncTimer, Sleep, TimedOut, Timeout};
+#[cfg(feature = "imxrt1060")]
+pub use capture::Capture;
+pub use capture::{CaptureEdge, Cycle, InputCaptureRegister};
+#[cfg(feature = "imxrt1060")]
+pub use compare::Compare;
+pub use compare::OutputMode;
+pub use delay::{CountDown, Delay};
+#[cfg(feature = "imxrt1060")]
+pub use frequency::FrequencyCounter;
+#[cfg(feature = "rtic")]
+pub use monotonic::RticMonotonic;
+pub use monotonic::{Instant, Monotonic};
+#[cfg(feature = "imxrt1060")]
+pub use pins::{CapturePin, ClockPin, ComparePin, Pin};
+pub use timers::{Action, QueueFull, TimerId, TimerService};
+
+/// The GPT1 and GPT2 interrupt numbers
+#[cfg(feature = "imxrt1010")]
+const INTERRUPTS: [usize; 2] = [30, 31];
+#[cfg(feature = "imxrt1060")]
+const INTERRUPTS: [usize; 2] = [100, 101];
+
 /// General purpose timers (GPT)
 pub struct GPT {
     /// Registers for this GPT instance
     registers: ral::gpt::Instance,
+    /// Counter frequency (Hz)
+    hz: u32,
 }
 
-/// GPT clock divider
+/// The clock that drives a GPT
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockSource {
+    /// The periodic clock
+    ///
+    /// See [`PERCLOCK_FREQUENCY_HZ`](../ccm/constant.PERCLOCK_FREQUENCY_HZ.html).
+    Peripheral,
+    /// The high frequency reference clock, running at `hz`
+    HighFrequencyReference { hz: u32 },
+    /// The 24 MHz crystal oscillator, divided by `prescaler`
+    ///
+    /// `prescaler` is from 1 to 16. It's applied before the main prescaler.
+    Oscillator { prescaler: u32 },
+    /// The external clock pin, running at `hz`
+    External { hz: u32 },
+}
+
+/// A GPT clock configuration
 ///
-/// This crystal oscillator is very sensitive. Not all values
-/// seem to work. 5 is one of them that does. So is 3. 10 does
-/// not work. The field is supposed to support values up to 0xF.
+/// The counter runs at the source's frequency, divided by `prescaler`. The
+/// default configuration is the periodic clock, undivided.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockConfig {
+    /// The clock source
+    pub source: ClockSource,
+    /// The main prescaler, from 1 to 4096
+    pub prescaler: u32,
+}
+
+impl Default for ClockConfig {
+    fn default() -> Self {
+        ClockConfig {
+            source: ClockSource::Peripheral,
+            prescaler: 1,
+        }
+    }
+}
+
+impl ClockConfig {
+    /// Returns the counter frequency (Hz)
+    ///
+    /// Returns 0 if a prescaler is 0.
+    pub fn frequency_hz(&self) -> u32 {
+        let source_hz = match self.source {
+            ClockSource::Peripheral => Some(crate::ccm::PERCLOCK_FREQUENCY_HZ),
+            ClockSource::HighFrequencyReference { hz } | ClockSource::External { hz } => Some(hz),
+            ClockSource::Oscillator { prescaler } => {
+                crate::ccm::OSCILLATOR_FREQUENCY_HZ.checked_div(prescaler)
+            }
+        };
+        source_hz
+            .and_then(|hz| hz.checked_div(self.prescaler))
+            .unwrap_or(0)
+    }
+}
+
+/// When the GPT counts, and how it starts
 ///
-/// The seL4 project also notes issues with this divider value.
-/// Can't find anything in the errata...
-const DIVIDER: u32 = 5;
+/// Each `bool` that names a mode keeps the counter running in that mode. The
+/// default configuration only runs in wait mode, and keeps the count when the GPT
+/// is re-enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunConfig {
+    /// Run while the core is halted by a debugger (`DBGEN`)
+    pub debug: bool,
+    /// Run in doze mode (`DOZEEN`)
+    pub doze: bool,
+    /// Run in wait mode (`WAITEN`)
+    pub wait: bool,
+    /// Run in stop mode (`STOPEN`)
+    ///
+    /// The GPT's clock source must also run in stop mode.
+    pub stop: bool,
+    /// Reset the counter when the GPT is enabled (`ENMOD`)
+    pub reset_on_enable: bool,
+}
 
-/// GPT effective frequency
-const CLOCK_HZ: u32 = crate::ccm::PERCLOCK_FREQUENCY_HZ / DIVIDER;
-const _CLOCK_PERIOD_US: u32 = 1_000_000u32 / CLOCK_HZ;
-const _STATIC_ASSERT: [u32; 1] = [0; (_CLOCK_PERIOD_US == 5) as usize];
+impl Default for RunConfig {
+    fn default() -> Self {
+        RunConfig {
+            debug: false,
+            doze: false,
+            wait: true,
+            stop: false,
+            reset_on_enable: false,
+        }
+    }
+}
 
 /// An output compare register (OCR)
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -30,6 +141,33 @@ pub enum OutputCompareRegister {
     Three,
 }
 
+bitflags::bitflags! {
+    /// GPT events
+    ///
+    /// Events are both interrupt sources and statuses. Each event has the same bit
+    /// in the interrupt register, `IR`, and the status register, `SR`.
+    pub struct Events : u32 {
+        /// Output compare 1
+        const OUTPUT_COMPARE_1 = 1 << 0;
+        /// Output compare 2
+        const OUTPUT_COMPARE_2 = 1 << 1;
+        /// Output compare 3
+        const OUTPUT_COMPARE_3 = 1 << 2;
+        /// Input capture 1
+        const INPUT_CAPTURE_1 = 1 << 3;
+        /// Input capture 2
+        const INPUT_CAPTURE_2 = 1 << 4;
+        /// The counter rolled over
+        const ROLLOVER = 1 << 5;
+    }
+}
+
+impl From<OutputCompareRegister> for Events {
+    fn from(output: OutputCompareRegister) -> Events {
+        Events::from_bits_truncate(1 << (output as u32))
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 /// Possible modes of the GPT
 pub enum Mode {
@@ -47,31 +185,124 @@ pub enum Mode {
 
 impl GPT {
     /// Create a new `GPT` from a RAL GPT instance
-    pub fn new(gpt: ral::gpt::Instance, _: &crate::ccm::PerClock<ral::gpt::Instance>) -> Self {
+    ///
+    /// The GPT applies `run` and enables the counter in one write.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a prescaler in `clock` is out of range, or if the counter frequency
+    /// is 0 Hz.
+    pub fn new(
+        gpt: ral::gpt::Instance,
+        _: &crate::ccm::PerClock<ral::gpt::Instance>,
+        clock: ClockConfig,
+        run: RunConfig,
+    ) -> Self {
+        use ral::gpt::CR::CLKSRC::RW::{CLKSRC_1, CLKSRC_2, CLKSRC_3, CLKSRC_5};
+
+        assert!(
+            (1..=4096).contains(&clock.prescaler),
+            "GPT prescaler must be from 1 to 4096"
+        );
+        let (clksrc, prescaler24m) = match clock.source {
+            ClockSource::Peripheral => (CLKSRC_1, 1),
+            ClockSource::HighFrequencyReference { .. } => (CLKSRC_2, 1),
+            ClockSource::External { .. } => (CLKSRC_3, 1),
+            ClockSource::Oscillator { prescaler } => {
+                assert!(
+                    (1..=16).contains(&prescaler),
+                    "GPT oscillator prescaler must be from 1 to 16"
+                );
+                (CLKSRC_5, prescaler)
+            }
+        };
+        assert!(
+            clock.frequency_hz() > 0,
+            "GPT clock frequency must be non-zero"
+        );
+
+        ral::write_reg!(ral::gpt, gpt, CR, EN: 0);
         ral::write_reg!(
             ral::gpt,
             gpt,
             CR,
-            EN_24M: 1, // Enable crystal oscillator
-            CLKSRC: 0b101 // Crystal Oscillator
+            EN_24M: (clksrc == CLKSRC_5) as u32,
+            CLKSRC: clksrc
         );
-        ral::write_reg!(ral::gpt, gpt, PR, PRESCALER24M: DIVIDER - 1);
-
-        // Clear all statuses
-        ral::write_reg!(ral::gpt, gpt, SR, 0b11_1111);
-        ral::modify_reg!(
-            ral::gpt, gpt, IR,
-            ROVIE: 0 // Rollover interrupt disabled
+        ral::write_reg!(
+            ral::gpt,
+            gpt,
+            PR,
+            PRESCALER24M: prescaler24m - 1,
+            PRESCALER: clock.prescaler - 1
         );
+
+        // Clear all statuses, and disable all interrupts
+        ral::write_reg!(ral::gpt, gpt, SR, Events::all().bits());
+        ral::write_reg!(ral::gpt, gpt, IR, 0);
         ral::modify_reg!(
             ral::gpt, gpt, CR,
             FRR: 1, // Free-running mode, no matter the output compare channel
-            WAITEN: 1, // Run in wait mode
-            ENMOD: 0, // Counter maintains value when disabled
+            DBGEN: run.debug as u32,
+            DOZEEN: run.doze as u32,
+            WAITEN: run.wait as u32,
+            STOPEN: run.stop as u32,
+            ENMOD: run.reset_on_enable as u32,
             EN: 1 // Start the timer
         );
 
-        GPT { registers: gpt }
+        GPT {
+            registers: gpt,
+            hz: clock.frequency_hz(),
+        }
+    }
+
+    /// Returns the GPT instance number, starting with '1'
+    #[cfg(any(feature = "imxrt1010", feature = "imxrt1060"))]
+    fn module(&self) -> usize {
+        let registers: *const ral::gpt::RegisterBlock = &*self.registers;
+        if registers == ral::gpt::GPT2 {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Pend the GPT's interrupt in the NVIC
+    ///
+    /// The interrupt handler runs once the interrupt is unmasked, even though no
+    /// GPT status flag is set.
+    pub(super) fn pend_interrupt(&self) {
+        #[cfg(any(feature = "imxrt1010", feature = "imxrt1060"))]
+        {
+            /// NVIC interrupt set-pending registers
+            const NVIC_ISPR: *mut u32 = 0xE000_E200 as *mut u32;
+            let irq = INTERRUPTS[self.module() - 1];
+            // Safety: ISPR is write-1-to-set, so this atomic write only affects
+            // the GPT's interrupt
+            unsafe { core::ptr::write_volatile(NVIC_ISPR.add(irq / 32), 1 << (irq % 32)) };
+        }
+    }
+
+    /// Returns the run configuration
+    pub fn run_config(&self) -> RunConfig {
+        let (debug, doze, wait, stop, reset_on_enable) = ral::read_reg!(
+            ral::gpt,
+            self.registers,
+            CR,
+            DBGEN,
+            DOZEEN,
+            WAITEN,
+            STOPEN,
+            ENMOD
+        );
+        RunConfig {
+            debug: debug != 0,
+            doze: doze != 0,
+            wait: wait != 0,
+            stop: stop != 0,
+            reset_on_enable: reset_on_enable != 0,
+        }
     }
 
     /// Returns the current mode of the GPT
@@ -134,20 +365,12 @@ impl GPT {
     /// Set an output compare register to trigger on the next `count` value of the
     /// counter.
     pub fn set_output_compare_count(&mut self, output: OutputCompareRegister, count: u32) {
-        match output {
-            OutputCompareRegister::One => ral::write_reg!(ral::gpt, self.registers, OCR1, count),
-            OutputCompareRegister::Two => ral::write_reg!(ral::gpt, self.registers, OCR2, count),
-            OutputCompareRegister::Three => ral::write_reg!(ral::gpt, self.registers, OCR3, count),
-        }
+        ral::write_reg!(ral::gpt, self.registers, OCR[output as usize], count);
     }
 
     /// Returns the current output compare count for the specified register
     pub fn output_compare_count(&self, output: OutputCompareRegister) -> u32 {
-        match output {
-            OutputCompareRegister::One => ral::read_reg!(ral::gpt, self.registers, OCR1),
-            OutputCompareRegister::Two => ral::read_reg!(ral::gpt, self.registers, OCR2),
-            OutputCompareRegister::Three => ral::read_reg!(ral::gpt, self.registers, OCR3),
-        }
+        ral::read_reg!(ral::gpt, self.registers, OCR[output as usize])
     }
 
     /// Returns a handle that can query and modify the output compare status for the provided output
@@ -155,15 +378,72 @@ impl GPT {
         OutputCompareStatus { gpt: self, output }
     }
 
+    /// Enable or disable the interrupts for `events`
+    ///
+    /// Interrupts for other events are unchanged.
+    pub fn set_interrupt_enable(&mut self, events: Events, enable: bool) {
+        ral::modify_reg!(ral::gpt, self.registers, IR, |ir| if enable {
+            ir | events.bits()
+        } else {
+            ir & !events.bits()
+        });
+    }
+
+    /// Returns the events that signal an interrupt
+    pub fn interrupts_enabled(&self) -> Events {
+        Events::from_bits_truncate(ral::read_reg!(ral::gpt, self.registers, IR))
+    }
+
+    /// Returns the events that have happened
+    ///
+    /// Events are flagged whether or not their interrupts are enabled.
+    pub fn status(&self) -> Events {
+        Events::from_bits_truncate(ral::read_reg!(ral::gpt, self.registers, SR))
+    }
+
+    /// Clear the status flags of `events`
+    ///
+    /// It's necessary to clear an event's flag when the event signals an interrupt.
+    /// Flags of other events are unchanged.
+    pub fn clear_status(&mut self, events: Events) {
+        ral::write_reg!(ral::gpt, self.registers, SR, events.bits());
+    }
+
+    /// Returns the counter frequency (Hz)
+    pub fn clock_frequency_hz(&self) -> u32 {
+        self.hz
+    }
+
     /// Returns the clock period as a duration
     ///
     /// This represents the resolution of the clock. The maximum measurement
-    /// interval is `clock_period() * u32::max_value()`.
+    /// interval is `clock_period() * u32::MAX`. The period is rounded down to a
+    /// whole nanosecond; use [`ticks_to_duration`](#method.ticks_to_duration) to
+    /// convert counts without accumulating that error.
     pub fn clock_period(&self) -> Duration {
-        Duration::from_nanos((1_000_000_000u32 / CLOCK_HZ).into())
+        Duration::from_nanos(NANOS_PER_SECOND / u64::from(self.hz))
+    }
+
+    /// Returns the duration of `ticks` counts
+    pub fn ticks_to_duration(&self, ticks: u64) -> Duration {
+        let hz = u64::from(self.hz);
+        Duration::new(ticks / hz, ((ticks % hz) * NANOS_PER_SECOND / hz) as u32)
+    }
+
+    /// Returns the number of counts in `duration`, rounded down
+    ///
+    /// Saturates at `u64::MAX`.
+    pub fn duration_to_ticks(&self, duration: Duration) -> u64 {
+        let hz = u64::from(self.hz);
+        duration
+            .as_secs()
+            .saturating_mul(hz)
+            .saturating_add(u64::from(duration.subsec_nanos()) * hz / NANOS_PER_SECOND)
     }
 }
 
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+
 /// A handle to evaluate and modify the output compare status
 pub struct OutputCompareStatus<'a> {
     gpt: &'a mut GPT,
@@ -173,8 +453,7 @@ pub struct OutputCompareStatus<'a> {
 impl<'a> OutputCompareStatus<'a> {
     /// Returns true if this output compare has triggered
     pub fn is_set(&self) -> bool {
-        let sr = ral::read_reg!(ral::gpt, self.gpt.registers, SR);
-        sr & (1 << (self.output as u32)) != 0
+        self.gpt.status().contains(self.output.into())
     }
 
     /// Clear the output compare status flag
@@ -182,6 +461,42 @@ impl<'a> OutputCompareStatus<'a> {
     /// It's necessary to clear the flag when the comparison has triggered
     /// an interrupt.
     pub fn clear(&mut self) {
-        ral::write_reg!(ral::gpt, self.gpt.registers, SR, 1 << (self.output as u32));
+        self.gpt.clear_status(self.output.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClockConfig, ClockSource};
+
+    #[test]
+    fn frequency_hz() {
+        let clock = ClockConfig {
+            source: ClockSource::External { hz: 1_000 },
+            prescaler: 4,
+        };
+        assert_eq!(clock.frequency_hz(), 250);
+        let clock = ClockConfig {
+            source: ClockSource::Oscillator { prescaler: 2 },
+            prescaler: 3,
+        };
+        assert_eq!(
+            clock.frequency_hz(),
+            crate::ccm::OSCILLATOR_FREQUENCY_HZ / 6
+        );
+    }
+
+    #[test]
+    fn frequency_hz_zero_prescaler() {
+        let clock = ClockConfig {
+            source: ClockSource::Peripheral,
+            prescaler: 0,
+        };
+        assert_eq!(clock.frequency_hz(), 0);
+        let clock = ClockConfig {
+            source: ClockSource::Oscillator { prescaler: 0 },
+            prescaler: 1,
+        };
+        assert_eq!(clock.frequency_hz(), 0);
     }
 }
@@ -0,0 +1,254 @@
+//! Async delays and timeouts
+//!
+//! An [`AsyncTimer`] wakes tasks at their deadlines. It tracks up to `N` pending
+//! delays, and programs the three nearest deadlines into the GPT's three output
+//! compare registers. Call [`on_interrupt`] from the GPT's interrupt handler.
+//!
+//! The futures only need a `Waker`, so they work with any executor.
+//!
+//! [`AsyncTimer`]: struct.AsyncTimer.html
+//! [`on_interrupt`]: struct.AsyncTimer.html#method.on_interrupt
+
+use super::{monotonic::compare_count, Events, Instant, Monotonic, OutputCompareRegister};
+use crate::ral;
+use core::{
+    cell::RefCell,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+use cortex_m::interrupt::{self, Mutex};
+
+const OUTPUTS: [OutputCompareRegister; 3] = [
+    OutputCompareRegister::One,
+    OutputCompareRegister::Two,
+    OutputCompareRegister::Three,
+];
+
+/// A pending delay
+struct Slot {
+    deadline: Instant,
+    waker: Waker,
+}
+
+/// A GPT-backed async timer, supporting up to `N` concurrent delays
+///
+/// All methods take `&self`, and you may call them from any context. If more than
+/// `N` delays are pending, the extra delays wake their tasks on every poll until a
+/// slot frees up. They're still correct, just not efficient.
+pub struct AsyncTimer<const N: usize> {
+    monotonic: Monotonic,
+    slots: Mutex<RefCell<[Option<Slot>; N]>>,
+}
+
+impl<const N: usize> AsyncTimer<N> {
+    /// Create an async timer
+    ///
+    /// This enables the GPT's rollover and output compare interrupts.
+    pub fn new(monotonic: Monotonic) -> Self {
+        let events = OUTPUTS
+            .iter()
+            .fold(Events::ROLLOVER, |events, &output| events | output.into());
+        ral::write_reg!(ral::gpt, monotonic.gpt.registers, IR, events.bits());
+        AsyncTimer {
+            monotonic,
+            slots: Mutex::new(RefCell::new(core::array::from_fn(|_| None))),
+        }
+    }
+
+    /// Returns the monotonic
+    pub fn monotonic(&self) -> &Monotonic {
+        &self.monotonic
+    }
+
+    /// Release the monotonic
+    ///
+    /// The GPT's interrupts are still enabled.
+    pub fn release(self) -> Monotonic {
+        self.monotonic
+    }
+
+    /// Returns the current time
+    pub fn now(&self) -> Instant {
+        self.monotonic.now()
+    }
+
+    /// Wait for `duration`
+    pub fn delay(&self, duration: Duration) -> Sleep<'_, N> {
+        let deadline = self
+            .monotonic
+            .checked_add(self.now(), duration)
+            .unwrap_or_else(|| Instant::from_ticks(u64::MAX));
+        self.delay_until(deadline)
+    }
+
+    /// Wait until `deadline`
+    ///
+    /// If `deadline` has passed, the future is immediately ready.
+    pub fn delay_until(&self, deadline: Instant) -> Sleep<'_, N> {
+        Sleep {
+            timer: self,
+            deadline,
+            slot: None,
+        }
+    }
+
+    /// Wait for `future`, but no longer than `duration`
+    ///
+    /// If `future` isn't ready within `duration`, the returned future drops
+    /// `future` and returns `Err(TimedOut)`.
+    pub fn with_timeout<F: Future>(&self, duration: Duration, future: F) -> Timeout<'_, F, N> {
+        Timeout {
+            future,
+            sleep: self.delay(duration),
+        }
+    }
+
+    /// Handle the GPT interrupt
+    ///
+    /// Call this from the GPT interrupt handler. This wakes the tasks of all
+    /// expired delays, and programs the next deadlines.
+    pub fn on_interrupt(&self) {
+        self.monotonic.on_interrupt();
+        interrupt::free(|cs| self.schedule(&mut self.slots.borrow(cs).borrow_mut()));
+    }
+
+    /// Wake the tasks of expired delays
+    fn wake_expired(&self, slots: &mut [Option<Slot>; N]) {
+        let now = self.now();
+        for slot in slots.iter_mut().flatten() {
+            if slot.deadline <= now {
+                // Keep the slot until its future is ready or dropped
+                slot.deadline = Instant::from_ticks(u64::MAX);
+                slot.waker.wake_by_ref();
+            }
+        }
+    }
+
+    /// Wake the tasks of expired delays, and program the nearest deadlines into
+    /// the output compare registers
+    ///
+    /// Call this in a critical section.
+    fn schedule(&self, slots: &mut [Option<Slot>; N]) {
+        self.wake_expired(slots);
+        let mut nearest = [Instant::from_ticks(u64::MAX); 3];
+        for slot in slots.iter().flatten() {
+            let mut deadline = slot.deadline;
+            for candidate in nearest.iter_mut() {
+                if deadline < *candidate {
+                    core::mem::swap(&mut deadline, candidate);
+                }
+            }
+        }
+
+        let registers = &self.monotonic.gpt.registers;
+        let now = self.now();
+        for (output, deadline) in OUTPUTS.iter().zip(nearest.iter()) {
+            let event = Events::from(*output).bits();
+            ral::write_reg!(ral::gpt, registers, SR, event);
+            if deadline.ticks() == u64::MAX {
+                ral::modify_reg!(ral::gpt, registers, IR, |ir| ir & !event);
+            } else {
+                let compare = compare_count(now, *deadline);
+                ral::write_reg!(ral::gpt, registers, OCR[*output as usize], compare);
+                ral::modify_reg!(ral::gpt, registers, IR, |ir| ir | event);
+            }
+        }
+        // The counter may have passed a deadline before we wrote its compare count
+        self.wake_expired(slots);
+    }
+}
+
+/// A future that's ready at its deadline
+///
+/// Use [`delay`](struct.AsyncTimer.html#method.delay) or
+/// [`delay_until`](struct.AsyncTimer.html#method.delay_until) to create a `Sleep`.
+pub struct Sleep<'a, const N: usize> {
+    timer: &'a AsyncTimer<N>,
+    deadline: Instant,
+    slot: Option<usize>,
+}
+
+impl<const N: usize> Sleep<'_, N> {
+    /// Returns the deadline
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    fn free_slot(&mut self, slots: &mut [Option<Slot>; N]) {
+        if let Some(slot) = self.slot.take() {
+            slots[slot] = None;
+            self.timer.schedule(slots);
+        }
+    }
+}
+
+impl<const N: usize> Future for Sleep<'_, N> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = &mut *self;
+        interrupt::free(|cs| {
+            let mut slots = this.timer.slots.borrow(cs).borrow_mut();
+            if this.timer.now() >= this.deadline {
+                this.free_slot(&mut slots);
+                return Poll::Ready(());
+            }
+
+            let slot = this.slot.or_else(|| slots.iter().position(Option::is_none));
+            match slot {
+                Some(slot) => {
+                    this.slot = Some(slot);
+                    slots[slot] = Some(Slot {
+                        deadline: this.deadline,
+                        waker: cx.waker().clone(),
+                    });
+                    this.timer.schedule(&mut slots);
+                }
+                None => cx.waker().wake_by_ref(),
+            }
+            Poll::Pending
+        })
+    }
+}
+
+impl<const N: usize> Drop for Sleep<'_, N> {
+    fn drop(&mut self) {
+        if self.slot.is_some() {
+            interrupt::free(|cs| {
+                let mut slots = self.timer.slots.borrow(cs).borrow_mut();
+                self.free_slot(&mut slots);
+            });
+        }
+    }
+}
+
+/// The future didn't finish before the timeout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimedOut;
+
+/// A future with a timeout
+///
+/// Use [`with_timeout`](struct.AsyncTimer.html#method.with_timeout) to create a
+/// `Timeout`.
+pub struct Timeout<'a, F, const N: usize> {
+    future: F,
+    sleep: Sleep<'a, N>,
+}
+
+impl<F: Future, const N: usize> Future for Timeout<'_, F, N> {
+    type Output = Result<F::Output, TimedOut>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: `future` is structurally pinned. We never move it, and
+        // `Timeout` doesn't implement `Unpin` unless `F` does. `sleep` isn't
+        // pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+        if let Poll::Ready(output) = future.poll(cx) {
+            return Poll::Ready(Ok(output));
+        }
+        Pin::new(&mut this.sleep).poll(cx).map(|_| Err(TimedOut))
+    }
+}
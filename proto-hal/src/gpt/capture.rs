@@ -0,0 +1,254 @@
+//! GPT input capture
+//!
+//! An input capture channel copies the GPT counter into an input capture register
+//! when its pin sees an edge. Use the captured counts to measure the period, pulse
+//! width, and duty cycle of a signal.
+
+#[cfg(feature = "imxrt1060")]
+use super::{pins, CapturePin};
+use super::{Mode, OutputCompareRegister, GPT};
+#[cfg(feature = "imxrt1060")]
+use crate::iomuxc::consts::Unsigned;
+use crate::ral;
+use core::time::Duration;
+
+/// An input capture register (ICR)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InputCaptureRegister {
+    One,
+    Two,
+}
+
+/// The edges that trigger a capture
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CaptureEdge {
+    /// Capture on the rising edge
+    Rising = 1,
+    /// Capture on the falling edge
+    Falling = 2,
+    /// Capture on both edges
+    Both = 3,
+}
+
+/// An input capture pin that's bound to a GPT
+///
+/// Use [`GPT::capture`](struct.GPT.html#method.capture) to create a `Capture`.
+#[cfg(feature = "imxrt1060")]
+pub struct Capture<P> {
+    pin: P,
+}
+
+#[cfg(feature = "imxrt1060")]
+impl<P> Capture<P>
+where
+    P: CapturePin,
+{
+    /// Returns the input capture register that holds this pin's captures
+    pub fn input(&self) -> InputCaptureRegister {
+        match P::Channel::USIZE {
+            1 => InputCaptureRegister::One,
+            2 => InputCaptureRegister::Two,
+            _ => unreachable!("GPT capture channels are 1 and 2"),
+        }
+    }
+
+    /// Stop capturing, and release the pin
+    pub fn release(self, gpt: &mut GPT) -> P {
+        gpt.set_input_capture_edge(self.input(), None);
+        self.pin
+    }
+}
+
+/// The captured counts of one cycle of a signal
+///
+/// The counts are the start of the cycle, the end of the pulse, and the start
+/// of the next cycle. Capture on both edges to collect them.
+///
+/// The counts don't record how often the counter rolled over, so a cycle may span
+/// at most one counter rollover. Check the [`ROLLOVER`](struct.Events.html) status
+/// between edges, and discard a cycle that saw more than one rollover. The methods
+/// return `None` when they can tell that the counts are inconsistent: a count is
+/// past the counter's top, or the pulse ends after the cycle.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Cycle {
+    /// The count at the edge that starts the pulse
+    pub start: u32,
+    /// The count at the edge that ends the pulse
+    pub pulse_end: u32,
+    /// The count at the edge that starts the next pulse
+    pub end: u32,
+}
+
+impl Cycle {
+    /// Returns the period of the signal
+    pub fn period(&self, gpt: &GPT) -> Option<Duration> {
+        let (_, period) = self.ticks(gpt.counter_top())?;
+        Some(gpt.ticks_to_duration(period.into()))
+    }
+
+    /// Returns the pulse width of the signal
+    pub fn pulse_width(&self, gpt: &GPT) -> Option<Duration> {
+        let (width, _) = self.ticks(gpt.counter_top())?;
+        Some(gpt.ticks_to_duration(width.into()))
+    }
+
+    /// Returns the fraction of the period that the pulse is active, from 0 to 1
+    ///
+    /// Returns 0 if the period is 0.
+    pub fn duty_cycle(&self, gpt: &GPT) -> Option<f32> {
+        let (width, period) = self.ticks(gpt.counter_top())?;
+        if period == 0 {
+            Some(0.0)
+        } else {
+            Some(width as f32 / period as f32)
+        }
+    }
+
+    /// Returns the pulse width and period ticks, for a counter that rolls over
+    /// after `top`
+    fn ticks(&self, top: u32) -> Option<(u32, u32)> {
+        let width = ticks_between(self.start, self.pulse_end, top)?;
+        let period = ticks_between(self.start, self.end, top)?;
+        if width <= period {
+            Some((width, period))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(feature = "imxrt1060")]
+impl GPT {
+    /// Bind an input capture pin to the GPT, and start capturing on `edge`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pin belongs to a different GPT.
+    pub fn capture<P>(&mut self, mut pin: P, edge: CaptureEdge) -> Capture<P>
+    where
+        P: CapturePin,
+    {
+        assert_eq!(
+            P::Module::USIZE,
+            self.module(),
+            "The capture pin belongs to a different GPT"
+        );
+        pins::prepare(&mut pin);
+        let capture = Capture { pin };
+        self.set_input_capture_edge(capture.input(), Some(edge));
+        capture
+    }
+}
+
+impl GPT {
+    /// Select the edges that trigger a capture, or `None` to disable the capture
+    pub fn set_input_capture_edge(
+        &mut self,
+        input: InputCaptureRegister,
+        edge: Option<CaptureEdge>,
+    ) {
+        let im = edge.map(|edge| edge as u32).unwrap_or(0);
+        match input {
+            InputCaptureRegister::One => ral::modify_reg!(ral::gpt, self.registers, CR, IM1: im),
+            InputCaptureRegister::Two => ral::modify_reg!(ral::gpt, self.registers, CR, IM2: im),
+        }
+    }
+
+    /// Returns the edges that trigger a capture, or `None` if the capture is disabled
+    pub fn input_capture_edge(&self, input: InputCaptureRegister) -> Option<CaptureEdge> {
+        let im = match input {
+            InputCaptureRegister::One => ral::read_reg!(ral::gpt, self.registers, CR, IM1),
+            InputCaptureRegister::Two => ral::read_reg!(ral::gpt, self.registers, CR, IM2),
+        };
+        match im {
+            1 => Some(CaptureEdge::Rising),
+            2 => Some(CaptureEdge::Falling),
+            3 => Some(CaptureEdge::Both),
+            _ => None,
+        }
+    }
+
+    /// Returns the count of the most recent capture
+    ///
+    /// Use [`status`](#method.status) to learn if there's a new capture.
+    pub fn input_capture_count(&self, input: InputCaptureRegister) -> u32 {
+        ral::read_reg!(ral::gpt, self.registers, ICR[input as usize])
+    }
+
+    /// Returns the time between two counts
+    ///
+    /// `later` may be smaller than `earlier` if the counter rolled over between
+    /// them. There may be at most one rollover; the counts can't show more. In
+    /// reset mode, the counter rolls over after it reaches output compare register 1.
+    ///
+    /// Returns `None` if either count is past the counter's top. That happens with
+    /// a stale count, or after lowering output compare register 1 in reset mode.
+    pub fn elapsed(&self, earlier: u32, later: u32) -> Option<Duration> {
+        let ticks = ticks_between(earlier, later, self.counter_top())?;
+        Some(self.ticks_to_duration(ticks.into()))
+    }
+
+    /// Returns the largest count before the counter rolls over
+    fn counter_top(&self) -> u32 {
+        match self.mode() {
+            Mode::FreeRunning => u32::MAX,
+            Mode::Reset => self.output_compare_count(OutputCompareRegister::One),
+        }
+    }
+}
+
+/// Returns the number of ticks from `earlier` to `later`, for a counter that
+/// rolls over to 0 after `top`
+///
+/// Returns `None` if either count is past `top`.
+fn ticks_between(earlier: u32, later: u32, top: u32) -> Option<u32> {
+    if earlier > top || later > top {
+        None
+    } else if later >= earlier {
+        Some(later - earlier)
+    } else {
+        Some((top - earlier) + later + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ticks_between, Cycle};
+
+    #[test]
+    fn test_ticks_between() {
+        assert_eq!(ticks_between(10, 25, u32::MAX), Some(15));
+        assert_eq!(ticks_between(u32::MAX - 4, 5, u32::MAX), Some(10));
+        assert_eq!(ticks_between(90, 9, 99), Some(19));
+        assert_eq!(ticks_between(7, 7, 99), Some(0));
+    }
+
+    #[test]
+    fn counts_past_top() {
+        assert_eq!(ticks_between(150, 20, 99), None);
+        assert_eq!(ticks_between(20, 150, 99), None);
+        assert_eq!(ticks_between(0, 99, 99), Some(99));
+    }
+
+    #[test]
+    fn cycle_ticks() {
+        let cycle = Cycle {
+            start: 90,
+            pulse_end: 95,
+            end: 10,
+        };
+        assert_eq!(cycle.ticks(99), Some((5, 20)));
+        let cycle = Cycle {
+            start: 90,
+            pulse_end: 20,
+            end: 10,
+        };
+        assert_eq!(cycle.ticks(99), None);
+        let cycle = Cycle {
+            start: 120,
+            pulse_end: 5,
+            end: 10,
+        };
+        assert_eq!(cycle.ticks(99), None);
+    }
+}
@@ -0,0 +1,123 @@
+//! GPT output compare pins
+//!
+//! When the counter reaches an output compare register, the GPT can change that
+//! channel's output compare pin without the CPU. In reset mode, toggling the pin
+//! on output compare 1 makes a square wave.
+
+#[cfg(feature = "imxrt1060")]
+use super::{pins, ComparePin};
+use super::{OutputCompareRegister, GPT};
+#[cfg(feature = "imxrt1060")]
+use crate::iomuxc::consts::Unsigned;
+use crate::ral;
+
+/// The action on an output compare pin when its output compare triggers
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OutputMode {
+    /// The pin is disconnected from the GPT
+    Disconnected = 0,
+    /// Toggle the pin
+    Toggle = 1,
+    /// Clear the pin
+    Clear = 2,
+    /// Set the pin
+    Set = 3,
+    /// Generate an active-low pulse, one GPT clock wide
+    Pulse = 4,
+}
+
+/// An output compare pin that's bound to a GPT
+///
+/// Use [`GPT::compare`](struct.GPT.html#method.compare) to create a `Compare`.
+#[cfg(feature = "imxrt1060")]
+pub struct Compare<P> {
+    pin: P,
+}
+
+#[cfg(feature = "imxrt1060")]
+impl<P> Compare<P>
+where
+    P: ComparePin,
+{
+    /// Returns the output compare register that drives this pin
+    pub fn output(&self) -> OutputCompareRegister {
+        match P::Channel::USIZE {
+            1 => OutputCompareRegister::One,
+            2 => OutputCompareRegister::Two,
+            3 => OutputCompareRegister::Three,
+            _ => unreachable!("GPT output compare channels are 1 through 3"),
+        }
+    }
+
+    /// Disconnect the pin from the GPT, and release the pin
+    pub fn release(self, gpt: &mut GPT) -> P {
+        gpt.set_output_mode(self.output(), OutputMode::Disconnected);
+        self.pin
+    }
+}
+
+#[cfg(feature = "imxrt1060")]
+impl GPT {
+    /// Bind an output compare pin to the GPT, and select its output mode
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pin belongs to a different GPT.
+    pub fn compare<P>(&mut self, mut pin: P, mode: OutputMode) -> Compare<P>
+    where
+        P: ComparePin,
+    {
+        assert_eq!(
+            P::Module::USIZE,
+            self.module(),
+            "The compare pin belongs to a different GPT"
+        );
+        pins::prepare(&mut pin);
+        let compare = Compare { pin };
+        self.set_output_mode(compare.output(), mode);
+        compare
+    }
+}
+
+impl GPT {
+    /// Select the action on the output compare pin when `output` triggers
+    pub fn set_output_mode(&mut self, output: OutputCompareRegister, mode: OutputMode) {
+        let om = mode as u32;
+        match output {
+            OutputCompareRegister::One => ral::modify_reg!(ral::gpt, self.registers, CR, OM1: om),
+            OutputCompareRegister::Two => ral::modify_reg!(ral::gpt, self.registers, CR, OM2: om),
+            OutputCompareRegister::Three => {
+                ral::modify_reg!(ral::gpt, self.registers, CR, OM3: om)
+            }
+        }
+    }
+
+    /// Returns the action on the output compare pin when `output` triggers
+    pub fn output_mode(&self, output: OutputCompareRegister) -> OutputMode {
+        let om = match output {
+            OutputCompareRegister::One => ral::read_reg!(ral::gpt, self.registers, CR, OM1),
+            OutputCompareRegister::Two => ral::read_reg!(ral::gpt, self.registers, CR, OM2),
+            OutputCompareRegister::Three => ral::read_reg!(ral::gpt, self.registers, CR, OM3),
+        };
+        match om {
+            0 => OutputMode::Disconnected,
+            1 => OutputMode::Toggle,
+            2 => OutputMode::Clear,
+            3 => OutputMode::Set,
+            _ => OutputMode::Pulse,
+        }
+    }
+
+    /// Perform the output mode's action on the output compare pin now
+    ///
+    /// This doesn't flag the output compare status, and doesn't signal an interrupt.
+    pub fn force_output(&mut self, output: OutputCompareRegister) {
+        match output {
+            OutputCompareRegister::One => ral::modify_reg!(ral::gpt, self.registers, CR, FO1: 1),
+            OutputCompareRegister::Two => ral::modify_reg!(ral::gpt, self.registers, CR, FO2: 1),
+            OutputCompareRegister::Three => {
+                ral::modify_reg!(ral::gpt, self.registers, CR, FO3: 1)
+            }
+        }
+    }
+}
@@ -0,0 +1,236 @@
+//! Blocking delays and count downs
+//!
+//! A [`Delay`] and a [`CountDown`] own a GPT, and run it in free-running mode.
+//! They track time by reading the counter, so they never change the counter or
+//! the output compare registers. Durations may be longer than one span of the
+//! 32-bit counter.
+//!
+//! [`Delay`]: struct.Delay.html
+//! [`CountDown`]: struct.CountDown.html
+
+use super::{Mode, GPT, NANOS_PER_SECOND};
+use core::time::Duration;
+
+impl GPT {
+    /// Returns the ticks since `last`, and updates `last` to the current count
+    ///
+    /// The counter must not complete a full span between calls.
+    pub(super) fn ticks_since(&self, last: &mut u32) -> u64 {
+        let now = self.count();
+        let elapsed = now.wrapping_sub(*last);
+        *last = now;
+        elapsed.into()
+    }
+
+    /// Run the counter in free-running mode
+    pub(super) fn free_run(&mut self) {
+        self.set_mode(Mode::FreeRunning);
+        self.set_enable(true);
+    }
+}
+
+/// A blocking delay
+///
+/// A delay lasts at least as long as requested. With the `embedded-hal-02`
+/// and `embedded-hal-1` features, `Delay` implements the embedded-hal delay traits.
+pub struct Delay {
+    gpt: GPT,
+}
+
+impl Delay {
+    /// Create a delay, and start the GPT counter in free-running mode
+    pub fn new(mut gpt: GPT) -> Self {
+        gpt.free_run();
+        Delay { gpt }
+    }
+
+    /// Release the GPT
+    pub fn release(self) -> GPT {
+        self.gpt
+    }
+
+    /// Block for at least `duration`
+    pub fn delay(&mut self, duration: Duration) {
+        // Add a tick, since the counter may be about to advance
+        let mut remaining =
+            ticks_at_least(duration, self.gpt.clock_frequency_hz()).saturating_add(1);
+        let mut last = self.gpt.count();
+        while remaining > 0 {
+            remaining = remaining.saturating_sub(self.gpt.ticks_since(&mut last));
+        }
+    }
+}
+
+/// Returns the ticks of a `hz` counter in `duration`, rounded up
+pub(super) fn ticks_at_least(duration: Duration, hz: u32) -> u64 {
+    let hz = u64::from(hz);
+    let subsec = (u64::from(duration.subsec_nanos()) * hz).div_ceil(NANOS_PER_SECOND);
+    duration.as_secs().saturating_mul(hz).saturating_add(subsec)
+}
+
+/// Returns the ticks of a `hz` counter in one `period`, rounded up to at least one tick
+fn period_ticks(period: Duration, hz: u32) -> u64 {
+    ticks_at_least(period, hz).max(1)
+}
+
+/// A periodic count down
+///
+/// After you [`start`](#method.start) the count down, [`expired`](#method.expired)
+/// returns `true` once per period. Call `expired` at least once per span of the
+/// 32-bit counter. With the `embedded-hal-02` feature, `CountDown` implements the
+/// embedded-hal `CountDown` and `Periodic` traits.
+pub struct CountDown {
+    gpt: GPT,
+    period: u64,
+    remaining: u64,
+    last: u32,
+}
+
+impl CountDown {
+    /// Create a count down, and start the GPT counter in free-running mode
+    ///
+    /// The count down doesn't expire until you start it.
+    pub fn new(mut gpt: GPT) -> Self {
+        gpt.free_run();
+        CountDown {
+            gpt,
+            period: 0,
+            remaining: 0,
+            last: 0,
+        }
+    }
+
+    /// Release the GPT
+    pub fn release(self) -> GPT {
+        self.gpt
+    }
+
+    /// Start counting down `period`
+    ///
+    /// This restarts a count down that's already running.
+    pub fn start(&mut self, period: Duration) {
+        self.period = period_ticks(period, self.gpt.clock_frequency_hz());
+        self.remaining = self.period;
+        self.last = self.gpt.count();
+    }
+
+    /// Returns `true` if a period expired since the last call
+    ///
+    /// When a period expires, the next period starts from the moment it expired,
+    /// so periods don't drift. Always returns `false` if the count down isn't
+    /// started.
+    pub fn expired(&mut self) -> bool {
+        if self.period == 0 {
+            return false;
+        }
+        let elapsed = self.gpt.ticks_since(&mut self.last);
+        if elapsed >= self.remaining {
+            let overshoot = (elapsed - self.remaining) % self.period;
+            self.remaining = self.period - overshoot;
+            true
+        } else {
+            self.remaining -= elapsed;
+            false
+        }
+    }
+}
+
+#[cfg(feature = "embedded-hal-02")]
+mod eh02 {
+    use super::{CountDown, Delay};
+    use core::time::Duration;
+    use embedded_hal_02::blocking::delay::{DelayMs, DelayUs};
+    use embedded_hal_02::timer;
+
+    macro_rules! impl_delay {
+        ($($ty:ty),+) => {
+            $(
+                impl DelayUs<$ty> for Delay {
+                    fn delay_us(&mut self, us: $ty) {
+                        self.delay(Duration::from_micros(us.into()));
+                    }
+                }
+
+                impl DelayMs<$ty> for Delay {
+                    fn delay_ms(&mut self, ms: $ty) {
+                        self.delay(Duration::from_millis(ms.into()));
+                    }
+                }
+            )+
+        };
+    }
+
+    impl_delay!(u8, u16, u32);
+
+    impl timer::CountDown for CountDown {
+        type Time = Duration;
+
+        fn start<T>(&mut self, count: T)
+        where
+            T: Into<Duration>,
+        {
+            CountDown::start(self, count.into());
+        }
+
+        fn wait(&mut self) -> nb::Result<(), void::Void> {
+            if self.expired() {
+                Ok(())
+            } else {
+                Err(nb::Error::WouldBlock)
+            }
+        }
+    }
+
+    impl timer::Periodic for CountDown {}
+}
+
+#[cfg(feature = "embedded-hal-1")]
+mod eh1 {
+    use super::Delay;
+    use core::time::Duration;
+    use embedded_hal_1::delay::DelayNs;
+
+    impl DelayNs for Delay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.delay(Duration::from_nanos(ns.into()));
+        }
+
+        fn delay_us(&mut self, us: u32) {
+            self.delay(Duration::from_micros(us.into()));
+        }
+
+        fn delay_ms(&mut self, ms: u32) {
+            self.delay(Duration::from_millis(ms.into()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{period_ticks, ticks_at_least};
+    use core::time::Duration;
+
+    #[test]
+    fn sub_tick_durations_round_up() {
+        assert_eq!(ticks_at_least(Duration::from_nanos(1), 1_000_000), 1);
+        assert_eq!(ticks_at_least(Duration::from_nanos(999), 1_000_000), 1);
+        assert_eq!(ticks_at_least(Duration::from_millis(1), 100), 1);
+        assert_eq!(ticks_at_least(Duration::from_nanos(0), 1_000_000), 0);
+    }
+
+    #[test]
+    fn fractional_tick_durations_round_up() {
+        assert_eq!(ticks_at_least(Duration::from_nanos(1_500), 1_000_000), 2);
+        assert_eq!(ticks_at_least(Duration::from_micros(2), 1_000_000), 2);
+        assert_eq!(ticks_at_least(Duration::new(1, 1), 3), 4);
+        assert_eq!(ticks_at_least(Duration::from_millis(500), 3), 2);
+    }
+
+    #[test]
+    fn sub_tick_periods_dont_shorten_count_downs() {
+        assert_eq!(period_ticks(Duration::from_nanos(1_500), 1_000_000), 2);
+        assert_eq!(period_ticks(Duration::from_nanos(999), 1_000_000), 1);
+        assert_eq!(period_ticks(Duration::from_nanos(0), 1_000_000), 1);
+        assert_eq!(period_ticks(Duration::from_millis(500), 3), 2);
+    }
+}
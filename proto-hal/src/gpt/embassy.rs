@@ -0,0 +1,224 @@
+//! An `embassy-time` driver
+//!
+//! The driver keeps time with a [`Monotonic`](../struct.Monotonic.html), and
+//! implements alarms with the GPT's three output compare registers. You select the
+//! GPT at runtime:
+//!
+//! 1. Configure a GPT so that it counts at `embassy_time_driver::TICK_HZ`.
+//! 2. Pass the GPT to [`init`](fn.init.html) before using `embassy-time`.
+//! 3. Call [`on_interrupt`](fn.on_interrupt.html) from that GPT's interrupt
+//!    handler, and unmask the interrupt.
+//!
+//! Until you call `init`, the time is always zero, and alarms expire immediately.
+
+use super::{monotonic::compare_count, Events, Instant, Monotonic, OutputCompareRegister, GPT};
+use crate::ral;
+use core::cell::RefCell;
+use cortex_m::interrupt::{self, Mutex};
+use embassy_time_driver::{AlarmHandle, Driver};
+
+/// Alarm IDs index into this array
+const ALARM_OUTPUTS: [OutputCompareRegister; 3] = [
+    OutputCompareRegister::One,
+    OutputCompareRegister::Two,
+    OutputCompareRegister::Three,
+];
+
+type Callback = (fn(*mut ()), *mut ());
+
+#[derive(Clone, Copy)]
+struct Alarm {
+    /// `u64::MAX` when the alarm isn't set
+    timestamp: u64,
+    callback: Option<Callback>,
+}
+
+const IDLE: Alarm = Alarm {
+    timestamp: u64::MAX,
+    callback: None,
+};
+
+struct State {
+    monotonic: Option<Monotonic>,
+    allocated: u8,
+    alarms: [Alarm; 3],
+}
+
+// Safety: the driver never dereferences the callback contexts. It only passes
+// them back to their callbacks.
+unsafe impl Send for State {}
+
+struct GptDriver {
+    state: Mutex<RefCell<State>>,
+}
+
+embassy_time_driver::time_driver_impl!(static DRIVER: GptDriver = GptDriver {
+    state: Mutex::new(RefCell::new(State {
+        monotonic: None,
+        allocated: 0,
+        alarms: [IDLE; 3],
+    })),
+});
+
+/// Use `gpt` as the `embassy-time` driver
+///
+/// This takes over the GPT's counter, output compare registers and interrupts.
+/// Alarms that were set before this call are cancelled.
+///
+/// # Panics
+///
+/// Panics if the GPT counter frequency isn't `embassy_time_driver::TICK_HZ`.
+pub fn init(gpt: GPT) {
+    assert_eq!(
+        u64::from(gpt.clock_frequency_hz()),
+        embassy_time_driver::TICK_HZ,
+        "GPT clock frequency doesn't match the embassy-time tick rate"
+    );
+    let monotonic = Monotonic::new(gpt);
+    // Alarms enable their own interrupts
+    ral::write_reg!(
+        ral::gpt,
+        monotonic.gpt.registers,
+        IR,
+        Events::ROLLOVER.bits()
+    );
+    interrupt::free(|cs| {
+        let mut state = DRIVER.state.borrow(cs).borrow_mut();
+        for alarm in state.alarms.iter_mut() {
+            alarm.timestamp = u64::MAX;
+        }
+        state.monotonic = Some(monotonic);
+    });
+}
+
+/// Handle the driver's GPT interrupt
+///
+/// Call this from the interrupt handler of the GPT you passed to [`init`](fn.init.html).
+/// This calls the callbacks of expired alarms.
+pub fn on_interrupt() {
+    let mut expired: [Option<Callback>; 3] = [None; 3];
+    interrupt::free(|cs| {
+        let mut state = DRIVER.state.borrow(cs).borrow_mut();
+        let State {
+            monotonic, alarms, ..
+        } = &mut *state;
+        let monotonic = match monotonic {
+            Some(monotonic) => monotonic,
+            None => return,
+        };
+        monotonic.on_interrupt();
+
+        let pending = monotonic.gpt.status() & monotonic.gpt.interrupts_enabled();
+        for (id, (alarm, output)) in alarms.iter_mut().zip(ALARM_OUTPUTS.iter()).enumerate() {
+            if !pending.contains((*output).into()) {
+                continue;
+            }
+            let now = monotonic.now().ticks();
+            if now < alarm.timestamp {
+                arm(monotonic, alarm, *output, now);
+                // The counter may have passed the compare value before we wrote it.
+                if monotonic.now().ticks() < alarm.timestamp {
+                    continue;
+                }
+            }
+            disarm(monotonic, alarm, *output);
+            expired[id] = alarm.callback;
+        }
+    });
+
+    for (callback, ctx) in expired.iter().flatten() {
+        callback(*ctx);
+    }
+}
+
+/// Program the alarm's output compare register with the next compare value
+///
+/// If the alarm is more than one counter span away, the compare fires after one
+/// span, and the interrupt handler re-arms the alarm. The compare value is at least
+/// a few ticks after `now`. Check the time after arming, in case the counter passed
+/// the compare value before the write.
+fn arm(monotonic: &Monotonic, alarm: &Alarm, output: OutputCompareRegister, now: u64) {
+    let compare = compare_count(
+        Instant::from_ticks(now),
+        Instant::from_ticks(alarm.timestamp),
+    );
+    let registers = &monotonic.gpt.registers;
+    ral::write_reg!(ral::gpt, registers, OCR[output as usize], compare);
+    ral::write_reg!(ral::gpt, registers, SR, Events::from(output).bits());
+    ral::modify_reg!(ral::gpt, registers, IR, |ir| ir
+        | Events::from(output).bits());
+}
+
+/// Stop the alarm's output compare interrupt
+fn disarm(monotonic: &Monotonic, alarm: &mut Alarm, output: OutputCompareRegister) {
+    alarm.timestamp = u64::MAX;
+    let registers = &monotonic.gpt.registers;
+    ral::modify_reg!(ral::gpt, registers, IR, |ir| ir
+        & !Events::from(output).bits());
+    ral::write_reg!(ral::gpt, registers, SR, Events::from(output).bits());
+}
+
+impl GptDriver {
+    fn with_state<R>(&self, f: impl FnOnce(&mut State) -> R) -> R {
+        interrupt::free(|cs| f(&mut self.state.borrow(cs).borrow_mut()))
+    }
+}
+
+impl Driver for GptDriver {
+    fn now(&self) -> u64 {
+        self.with_state(|state| {
+            state
+                .monotonic
+                .as_ref()
+                .map_or(0, |monotonic| monotonic.now().ticks())
+        })
+    }
+
+    unsafe fn allocate_alarm(&self) -> Option<AlarmHandle> {
+        self.with_state(|state| {
+            if usize::from(state.allocated) < ALARM_OUTPUTS.len() {
+                let id = state.allocated;
+                state.allocated += 1;
+                Some(AlarmHandle::new(id))
+            } else {
+                None
+            }
+        })
+    }
+
+    fn set_alarm_callback(&self, alarm: AlarmHandle, callback: fn(*mut ()), ctx: *mut ()) {
+        self.with_state(|state| {
+            state.alarms[usize::from(alarm.id())].callback = Some((callback, ctx));
+        })
+    }
+
+    fn set_alarm(&self, alarm: AlarmHandle, timestamp: u64) -> bool {
+        self.with_state(|state| {
+            let State {
+                monotonic, alarms, ..
+            } = state;
+            let monotonic = match monotonic {
+                Some(monotonic) => monotonic,
+                None => return false,
+            };
+            let output = ALARM_OUTPUTS[usize::from(alarm.id())];
+            let alarm = &mut alarms[usize::from(alarm.id())];
+
+            let now = monotonic.now().ticks();
+            if timestamp <= now {
+                disarm(monotonic, alarm, output);
+                return false;
+            }
+            alarm.timestamp = timestamp;
+            arm(monotonic, alarm, output, now);
+
+            // The counter may have passed the compare value before we wrote it.
+            if monotonic.now().ticks() >= timestamp {
+                disarm(monotonic, alarm, output);
+                false
+            } else {
+                true
+            }
+        })
+    }
+}
@@ -0,0 +1,154 @@
+//! Frequency measurement
+//!
+//! A [`FrequencyCounter`] owns a GPT that counts edges on its external clock pin.
+//! A second GPT, running from a known clock, is the time reference. The counter
+//! measures either
+//!
+//! - the edges in a time window ([`measure`]), which suits high frequencies, or
+//! - the time of an edge window ([`measure_edges`]), which suits low frequencies.
+//!   The counter's output compare register 1 ends the window.
+//!
+//! Both measurements block until they finish.
+//!
+//! [`FrequencyCounter`]: struct.FrequencyCounter.html
+//! [`measure`]: struct.FrequencyCounter.html#method.measure
+//! [`measure_edges`]: struct.FrequencyCounter.html#method.measure_edges
+
+use super::{pins, ClockPin, Events, OutputCompareRegister, GPT};
+use crate::iomuxc::consts::Unsigned;
+use crate::ral;
+use core::time::Duration;
+
+/// Counts the edges of a signal on a GPT external clock pin
+pub struct FrequencyCounter<P> {
+    gpt: GPT,
+    pin: P,
+}
+
+/// The output compare register that ends an edge window
+const WINDOW: OutputCompareRegister = OutputCompareRegister::One;
+
+impl<P> FrequencyCounter<P>
+where
+    P: ClockPin,
+{
+    /// Create a frequency counter
+    ///
+    /// Create the GPT with an [`External`](enum.ClockSource.html#variant.External) clock
+    /// source and a prescaler of 1. The `hz` of the clock source isn't used. The
+    /// counter runs in free-running mode.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the GPT's clock source isn't the external clock, if its prescaler
+    /// isn't 1, or if the pin belongs to a different GPT.
+    pub fn new(mut gpt: GPT, mut pin: P) -> Self {
+        assert!(
+            ral::read_reg!(ral::gpt, gpt.registers, CR, CLKSRC == CLKSRC_3),
+            "The frequency counter GPT must use the external clock"
+        );
+        assert!(
+            ral::read_reg!(ral::gpt, gpt.registers, PR, PRESCALER == 0),
+            "The frequency counter GPT must use a prescaler of 1"
+        );
+        assert_eq!(
+            P::Module::USIZE,
+            gpt.module(),
+            "The clock pin belongs to a different GPT"
+        );
+        pins::prepare(&mut pin);
+        gpt.free_run();
+        FrequencyCounter { gpt, pin }
+    }
+
+    /// Release the GPT and the pin
+    pub fn release(self) -> (GPT, P) {
+        (self.gpt, self.pin)
+    }
+
+    /// Count edges for `window`, and return the frequency (Hz)
+    ///
+    /// `reference` times the window, and it must be enabled. The result is rounded
+    /// to the nearest hertz. The signal must not complete a full span of the 32-bit
+    /// counter during the window.
+    pub fn measure(&mut self, reference: &GPT, window: Duration) -> u32 {
+        let window = reference.duration_to_ticks(window).max(1);
+        let mut elapsed = 0;
+        let mut last = reference.count();
+        let start = self.gpt.count();
+        while elapsed < window {
+            elapsed += reference.ticks_since(&mut last);
+        }
+        let edges = self.gpt.count().wrapping_sub(start);
+        frequency_hz(edges.into(), reference.clock_frequency_hz(), elapsed)
+    }
+
+    /// Time `edges` edges, and return the frequency (Hz)
+    ///
+    /// `reference` times the window, and it must be enabled. The result is rounded
+    /// to the nearest hertz. Returns `None` if the signal doesn't complete `edges`
+    /// within `timeout`. Choose enough `edges` so that the signal can't complete
+    /// them before the counter sets up the window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `edges` is 0.
+    pub fn measure_edges(&mut self, reference: &GPT, edges: u32, timeout: Duration) -> Option<u32> {
+        assert!(edges > 0, "The edge window must have at least one edge");
+        let timeout = reference.duration_to_ticks(timeout);
+
+        // Synchronize with an edge, so that the window is whole edges
+        let mut last = reference.count();
+        let mut waited = 0;
+        let first = self.gpt.count();
+        let start = loop {
+            let count = self.gpt.count();
+            let elapsed = reference.ticks_since(&mut last);
+            if count != first {
+                break count;
+            }
+            waited += elapsed;
+            if waited > timeout {
+                return None;
+            }
+        };
+
+        self.gpt.clear_status(WINDOW.into());
+        self.gpt
+            .set_output_compare_count(WINDOW, start.wrapping_add(edges));
+        let mut elapsed = 0;
+        while !self.gpt.status().contains(Events::from(WINDOW)) {
+            elapsed += reference.ticks_since(&mut last);
+            if waited + elapsed > timeout {
+                return None;
+            }
+        }
+        elapsed += reference.ticks_since(&mut last);
+        self.gpt.clear_status(WINDOW.into());
+        Some(frequency_hz(
+            edges.into(),
+            reference.clock_frequency_hz(),
+            elapsed.max(1),
+        ))
+    }
+}
+
+/// Returns the frequency of `edges` over `ticks` of a `reference_hz` clock, rounded
+fn frequency_hz(edges: u64, reference_hz: u32, ticks: u64) -> u32 {
+    let hz =
+        (u128::from(edges) * u128::from(reference_hz) + u128::from(ticks / 2)) / u128::from(ticks);
+    hz.min(u32::MAX.into()) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::frequency_hz;
+
+    #[test]
+    fn test_frequency_hz() {
+        assert_eq!(frequency_hz(1_000, 1_000_000, 1_000_000), 1_000);
+        assert_eq!(frequency_hz(3, 1_000_000, 2_000_000), 2);
+        assert_eq!(frequency_hz(10, 24_000_000, 1_000), 240_000);
+        assert_eq!(frequency_hz(u64::MAX, u32::MAX, 1), u32::MAX);
+    }
+}
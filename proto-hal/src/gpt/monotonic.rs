@@ -0,0 +1,340 @@
+//! A 64-bit monotonic clock
+//!
+//! A [`Monotonic`] extends a GPT's 32-bit counter to 64 bits by counting
+//! rollovers. Call [`on_interrupt`](struct.Monotonic.html#method.on_interrupt)
+//! from the GPT's interrupt handler, and make sure the handler runs at least once
+//! per span of the 32-bit counter.
+//!
+//! With the `rtic` feature, [`RticMonotonic`] implements the RTIC `Monotonic` trait.
+//!
+//! [`Monotonic`]: struct.Monotonic.html
+//! [`RticMonotonic`]: struct.RticMonotonic.html
+
+use super::{Events, Mode, OutputCompareRegister, GPT};
+use crate::ral;
+use core::{
+    sync::atomic::{AtomicU32, Ordering},
+    time::Duration,
+};
+
+/// The output compare register that signals deadlines
+const DEADLINE: OutputCompareRegister = OutputCompareRegister::One;
+
+/// A point in time, measured in ticks of a [`Monotonic`](struct.Monotonic.html)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Instant(u64);
+
+impl Instant {
+    /// Create an instant from a tick count
+    pub const fn from_ticks(ticks: u64) -> Self {
+        Instant(ticks)
+    }
+
+    /// Returns the tick count of this instant
+    pub const fn ticks(self) -> u64 {
+        self.0
+    }
+
+    /// Returns the ticks from `earlier` to this instant, or `None` if `earlier`
+    /// is after this instant
+    pub fn checked_ticks_since(self, earlier: Instant) -> Option<u64> {
+        self.0.checked_sub(earlier.0)
+    }
+}
+
+/// A GPT-backed, 64-bit monotonic clock
+///
+/// All methods take `&self`, and you may call them from any context, including
+/// interrupt handlers of any priority.
+pub struct Monotonic {
+    pub(super) gpt: GPT,
+    /// The upper 32 bits of the count
+    epoch: AtomicU32,
+}
+
+// Safety: the shared methods only read GPT registers, or modify them in critical
+// sections. The GPT's shared methods only read registers.
+unsafe impl Sync for Monotonic {}
+
+impl Monotonic {
+    /// Create a monotonic clock, and start the GPT counter in free-running mode
+    ///
+    /// This enables the GPT's rollover and output compare 1 interrupts. The
+    /// monotonic continues from the GPT's current count.
+    pub fn new(mut gpt: GPT) -> Self {
+        gpt.set_enable(false);
+        gpt.set_mode(Mode::FreeRunning);
+        ral::write_reg!(ral::gpt, gpt.registers, IR, 0);
+        gpt.clear_status(Events::all());
+        ral::write_reg!(
+            ral::gpt,
+            gpt.registers,
+            IR,
+            (Events::ROLLOVER | Events::from(DEADLINE)).bits()
+        );
+        gpt.set_enable(true);
+        Monotonic {
+            gpt,
+            epoch: AtomicU32::new(0),
+        }
+    }
+
+    /// Release the GPT
+    ///
+    /// The GPT's interrupts are still enabled.
+    pub fn release(self) -> GPT {
+        self.gpt
+    }
+
+    /// Returns the GPT
+    ///
+    /// Use the GPT to convert between ticks and durations.
+    pub fn gpt(&self) -> &GPT {
+        &self.gpt
+    }
+
+    /// Returns the current time
+    pub fn now(&self) -> Instant {
+        cortex_m::interrupt::free(|_| {
+            let epoch = self.epoch.load(Ordering::Relaxed);
+            let count = self.gpt.count();
+            if self.gpt.status().contains(Events::ROLLOVER) {
+                // The counter rolled over, but we haven't handled the interrupt.
+                // Sample the counter again, in case it rolled over after the first read.
+                let count = self.gpt.count();
+                Instant(u64::from(epoch.wrapping_add(1)) << 32 | u64::from(count))
+            } else {
+                Instant(u64::from(epoch) << 32 | u64::from(count))
+            }
+        })
+    }
+
+    /// Returns the time elapsed since `earlier`
+    ///
+    /// Returns a zero duration if `earlier` is in the future.
+    pub fn elapsed(&self, earlier: Instant) -> Duration {
+        let ticks = self.now().checked_ticks_since(earlier).unwrap_or(0);
+        self.gpt.ticks_to_duration(ticks)
+    }
+
+    /// Returns the instant that's `duration` after `instant`
+    ///
+    /// Returns `None` if the instant overflows.
+    pub fn checked_add(&self, instant: Instant, duration: Duration) -> Option<Instant> {
+        instant
+            .0
+            .checked_add(self.gpt.duration_to_ticks(duration))
+            .map(Instant)
+    }
+
+    /// Handle the GPT interrupt
+    ///
+    /// Call this from the GPT interrupt handler. If the counter rolled over, this
+    /// clears the rollover flag and advances the epoch. It doesn't clear the deadline
+    /// flag.
+    pub fn on_interrupt(&self) {
+        cortex_m::interrupt::free(|_| {
+            if self.gpt.status().contains(Events::ROLLOVER) {
+                ral::write_reg!(ral::gpt, self.gpt.registers, SR, Events::ROLLOVER.bits());
+                let epoch = self.epoch.load(Ordering::Relaxed);
+                self.epoch.store(epoch.wrapping_add(1), Ordering::Relaxed);
+            }
+        });
+    }
+
+    /// Signal a deadline interrupt at `deadline`
+    ///
+    /// If `deadline` is more than one counter span away, the interrupt fires after
+    /// one span; check the time, and set the deadline again. If `deadline` has
+    /// passed, `set_deadline` pends the GPT interrupt in the NVIC. The deadline flag
+    /// isn't set in that case, so compare [`now`](#method.now) with the deadline in
+    /// the interrupt handler.
+    pub fn set_deadline(&self, deadline: Instant) {
+        cortex_m::interrupt::free(|_| {
+            let mut margin = MIN_DEADLINE_TICKS;
+            loop {
+                let now = self.now();
+                if now >= deadline {
+                    self.gpt.pend_interrupt();
+                    return;
+                }
+                let compare = compare_count_with_margin(now, deadline, margin);
+                ral::write_reg!(
+                    ral::gpt,
+                    self.gpt.registers,
+                    OCR[DEADLINE as usize],
+                    compare
+                );
+                // If the counter passed the compare count before the write landed,
+                // the interrupt wouldn't fire for another counter span. Try again
+                // with a larger margin. The deadline eventually passes, or the margin
+                // covers the time to write the compare count.
+                let start = now.0 as u32;
+                if self.gpt.count().wrapping_sub(start) < compare.wrapping_sub(start) {
+                    return;
+                }
+                margin = margin.saturating_mul(2);
+            }
+        });
+    }
+
+    /// Returns `true` if the deadline flag is set
+    pub fn deadline_reached(&self) -> bool {
+        self.gpt.status().contains(DEADLINE.into())
+    }
+
+    /// Clear the deadline flag
+    pub fn clear_deadline(&self) {
+        ral::write_reg!(
+            ral::gpt,
+            self.gpt.registers,
+            SR,
+            Events::from(DEADLINE).bits()
+        );
+    }
+}
+
+/// The minimum ticks between now and a deadline
+///
+/// The counter may advance while we compute and write a deadline. If the deadline
+/// is too close, the counter passes it before the write.
+const MIN_DEADLINE_TICKS: u32 = 2;
+
+/// Returns the output compare count that signals `deadline`
+///
+/// The count is at most one counter span after `now`, and at least
+/// `MIN_DEADLINE_TICKS` after `now`. Call this in a critical section, shortly
+/// after sampling `now`.
+pub(super) fn compare_count(now: Instant, deadline: Instant) -> u32 {
+    compare_count_with_margin(now, deadline, MIN_DEADLINE_TICKS)
+}
+
+/// Returns the output compare count that signals `deadline`, at least `margin`
+/// ticks after `now`
+fn compare_count_with_margin(now: Instant, deadline: Instant, margin: u32) -> u32 {
+    let ticks = match deadline.checked_ticks_since(now) {
+        Some(ticks) if ticks > u64::from(u32::MAX) => u32::MAX,
+        Some(ticks) => (ticks as u32).max(margin),
+        None => margin,
+    };
+    (now.0 as u32).wrapping_add(ticks)
+}
+
+#[cfg(feature = "rtic")]
+mod rtic {
+    use super::{Instant, Monotonic};
+    use crate::ral;
+
+    /// A [`Monotonic`](struct.Monotonic.html) for RTIC
+    ///
+    /// `HZ` is the GPT counter frequency. RTIC calls
+    /// [`on_interrupt`](struct.Monotonic.html#method.on_interrupt) for you.
+    pub struct RticMonotonic<const HZ: u32> {
+        monotonic: Monotonic,
+    }
+
+    impl<const HZ: u32> RticMonotonic<HZ> {
+        /// Create an RTIC monotonic
+        ///
+        /// # Panics
+        ///
+        /// Panics if the GPT counter frequency isn't `HZ`.
+        pub fn new(monotonic: Monotonic) -> Self {
+            assert_eq!(
+                monotonic.gpt().clock_frequency_hz(),
+                HZ,
+                "GPT clock frequency doesn't match the RTIC monotonic frequency"
+            );
+            RticMonotonic { monotonic }
+        }
+
+        /// Release the monotonic
+        pub fn release(self) -> Monotonic {
+            self.monotonic
+        }
+    }
+
+    impl<const HZ: u32> rtic_monotonic::Monotonic for RticMonotonic<HZ> {
+        // The rollover interrupt must always be enabled.
+        const DISABLE_INTERRUPT_ON_EMPTY_QUEUE: bool = false;
+
+        type Instant = fugit::TimerInstantU64<HZ>;
+        type Duration = fugit::TimerDurationU64<HZ>;
+
+        fn now(&mut self) -> Self::Instant {
+            Self::Instant::from_ticks(self.monotonic.now().ticks())
+        }
+
+        fn set_compare(&mut self, instant: Self::Instant) {
+            self.monotonic
+                .set_deadline(Instant::from_ticks(instant.ticks()));
+        }
+
+        fn clear_compare_flag(&mut self) {
+            self.monotonic.clear_deadline();
+        }
+
+        fn zero() -> Self::Instant {
+            Self::Instant::from_ticks(0)
+        }
+
+        unsafe fn reset(&mut self) {
+            let gpt = &self.monotonic.gpt;
+            cortex_m::interrupt::free(|_| {
+                // Reset the counter by enabling with ENMOD set, then restore ENMOD
+                let enmod = ral::read_reg!(ral::gpt, gpt.registers, CR, ENMOD);
+                ral::modify_reg!(ral::gpt, gpt.registers, CR, EN: 0, ENMOD: 1);
+                ral::modify_reg!(ral::gpt, gpt.registers, CR, EN: 1);
+                ral::modify_reg!(ral::gpt, gpt.registers, CR, ENMOD: enmod);
+                ral::write_reg!(ral::gpt, gpt.registers, SR, super::Events::ROLLOVER.bits());
+                self.monotonic.epoch.store(0, super::Ordering::Relaxed);
+            });
+        }
+
+        fn on_interrupt(&mut self) {
+            self.monotonic.on_interrupt();
+        }
+    }
+}
+
+#[cfg(feature = "rtic")]
+pub use self::rtic::RticMonotonic;
+
+#[cfg(test)]
+mod tests {
+    use super::{compare_count, compare_count_with_margin, Instant, MIN_DEADLINE_TICKS};
+
+    #[test]
+    fn compare_count_spans() {
+        let now = Instant::from_ticks(0x1_0000_0010);
+        assert_eq!(
+            compare_count(now, Instant::from_ticks(0x1_0000_0100)),
+            0x100
+        );
+        assert_eq!(
+            compare_count(now, Instant::from_ticks(0x3_0000_0000)),
+            0x10u32.wrapping_add(u32::MAX)
+        );
+    }
+
+    #[test]
+    fn compare_count_margin() {
+        let now = Instant::from_ticks(100);
+        assert_eq!(
+            compare_count(now, Instant::from_ticks(101)),
+            100 + MIN_DEADLINE_TICKS
+        );
+        assert_eq!(
+            compare_count(now, Instant::from_ticks(50)),
+            100 + MIN_DEADLINE_TICKS
+        );
+        assert_eq!(
+            compare_count_with_margin(now, Instant::from_ticks(110), 64),
+            164
+        );
+        assert_eq!(
+            compare_count_with_margin(now, Instant::from_ticks(200), 64),
+            200
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! GPT pins
+//!
+//! Each GPT has an external clock input, two input capture inputs, and three
+//! output compare outputs. The pin traits describe which GPT, and which signal,
+//! a pad carries. The GPT checks that a pin belongs to it before it uses the pin.
+//!
+//! The pin traits, and the GPT APIs that take pins, are only available on the
+//! i.MX RT 1060.
+
+use crate::iomuxc::{consts::Unsigned, Daisy, IOMUX};
+
+/// A pad that carries a GPT signal
+///
+/// # Safety
+///
+/// `ALT` must select a signal of GPT `Module` on the pad. If the signal is an input
+/// that's available on more than one pad, `DAISY` must select this pad.
+pub unsafe trait Pin: IOMUX {
+    /// The GPT instance; `U1` or `U2`
+    type Module: Unsigned;
+    /// The pad's alternate function for the GPT signal
+    const ALT: u32;
+    /// The input selection for this pad, if the signal needs one
+    const DAISY: Option<Daisy>;
+}
+
+/// A pad that can be a GPT input capture pin
+///
+/// # Safety
+///
+/// The pad's `ALT` must select input capture channel `Channel` of its GPT.
+pub unsafe trait CapturePin: Pin {
+    /// The input capture channel; `U1` or `U2`
+    type Channel: Unsigned;
+}
+
+/// A pad that can be a GPT output compare pin
+///
+/// # Safety
+///
+/// The pad's `ALT` must select output compare channel `Channel` of its GPT.
+pub unsafe trait ComparePin: Pin {
+    /// The output compare channel; `U1`, `U2`, or `U3`
+    type Channel: Unsigned;
+}
+
+/// A pad that can be a GPT external clock pin
+///
+/// # Safety
+///
+/// The pad's `ALT` must select the clock input of its GPT.
+pub unsafe trait ClockPin: Pin {}
+
+/// Select the pad's alternate function, and its input selection
+pub(super) fn prepare<P: Pin>(pin: &mut P) {
+    crate::iomuxc::alternate(pin, P::ALT);
+    if let Some(daisy) = P::DAISY {
+        // Safety: the pin's implementation guarantees that the daisy selects this pad
+        unsafe { daisy.write() };
+    }
+}
+
+/// Implement `Pin` for a pad
+macro_rules! gpt_pin {
+    ($pad:ty, module: $module:ty, alt: $alt:expr, daisy: $daisy:expr) => {
+        unsafe impl Pin for $pad {
+            type Module = $module;
+            const ALT: u32 = $alt;
+            const DAISY: Option<Daisy> = $daisy;
+        }
+    };
+}
+
+mod imxrt1060 {
+    use super::{CapturePin, ClockPin, ComparePin, Daisy, Pin};
+    use crate::iomuxc::{
+        consts::{U1, U2, U3},
+        imxrt106x::ad_b1::{AD_B1_02, AD_B1_03, AD_B1_04, AD_B1_05, AD_B1_06, AD_B1_07},
+    };
+
+    // IOMUXC select input (daisy) registers, from the IOMUXC memory map in the
+    // i.MX RT1060 reference manual, chapter 11
+    const IOMUXC_GPT2_IPP_IND_CAPIN1_SELECT_INPUT: *mut u32 = 0x401F_8764 as *mut u32;
+    const IOMUXC_GPT2_IPP_IND_CAPIN2_SELECT_INPUT: *mut u32 = 0x401F_8768 as *mut u32;
+    const IOMUXC_GPT2_IPP_IND_CLKIN_SELECT_INPUT: *mut u32 = 0x401F_876C as *mut u32;
+
+    /// The `DAISY` value that selects the GPIO_AD_B1 pad, ALT 8
+    const SELECT_GPIO_AD_B1: u32 = 1;
+    const CAPIN1_DAISY: Daisy =
+        Daisy::new(IOMUXC_GPT2_IPP_IND_CAPIN1_SELECT_INPUT, SELECT_GPIO_AD_B1);
+    const CAPIN2_DAISY: Daisy =
+        Daisy::new(IOMUXC_GPT2_IPP_IND_CAPIN2_SELECT_INPUT, SELECT_GPIO_AD_B1);
+    const CLKIN_DAISY: Daisy =
+        Daisy::new(IOMUXC_GPT2_IPP_IND_CLKIN_SELECT_INPUT, SELECT_GPIO_AD_B1);
+
+    gpt_pin!(AD_B1_03, module: U2, alt: 8, daisy: Some(CAPIN1_DAISY));
+    gpt_pin!(AD_B1_04, module: U2, alt: 8, daisy: Some(CAPIN2_DAISY));
+
+    unsafe impl CapturePin for AD_B1_03 {
+        type Channel = U1;
+    }
+    unsafe impl CapturePin for AD_B1_04 {
+        type Channel = U2;
+    }
+
+    gpt_pin!(AD_B1_05, module: U2, alt: 8, daisy: None);
+    gpt_pin!(AD_B1_06, module: U2, alt: 8, daisy: None);
+    gpt_pin!(AD_B1_07, module: U2, alt: 8, daisy: None);
+
+    unsafe impl ComparePin for AD_B1_05 {
+        type Channel = U1;
+    }
+    unsafe impl ComparePin for AD_B1_06 {
+        type Channel = U2;
+    }
+    unsafe impl ComparePin for AD_B1_07 {
+        type Channel = U3;
+    }
+
+    gpt_pin!(AD_B1_02, module: U2, alt: 8, daisy: Some(CLKIN_DAISY));
+
+    unsafe impl ClockPin for AD_B1_02 {}
+}
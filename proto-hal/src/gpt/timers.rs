@@ -0,0 +1,329 @@
+//! Software timers
+//!
+//! A [`TimerService`] multiplexes many timers onto one [`Monotonic`]. It keeps
+//! the timers sorted by deadline, and programs the nearest deadline into the
+//! monotonic's output compare register. When a timer expires, the service calls
+//! its callback, or wakes its waker, from [`on_interrupt`].
+//!
+//! [`TimerService`]: struct.TimerService.html
+//! [`Monotonic`]: struct.Monotonic.html
+//! [`on_interrupt`]: struct.TimerService.html#method.on_interrupt
+
+use super::{delay::ticks_at_least, Instant, Monotonic};
+use core::{cell::RefCell, task::Waker, time::Duration};
+use cortex_m::interrupt::{self, Mutex};
+
+/// Identifies a timer in a [`TimerService`](struct.TimerService.html)
+///
+/// An ID is only valid until its timer expires or is cancelled. Stale IDs don't
+/// affect newer timers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerId {
+    slot: usize,
+    generation: u32,
+}
+
+/// What to do when a timer expires
+#[derive(Debug, Clone)]
+pub enum Action {
+    /// Call the function with the timer's ID
+    Callback(fn(TimerId)),
+    /// Wake the waker
+    Wake(Waker),
+}
+
+impl Action {
+    fn run(self, id: TimerId) {
+        match self {
+            Action::Callback(callback) => callback(id),
+            Action::Wake(waker) => waker.wake(),
+        }
+    }
+}
+
+/// The timer service has no free timers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueFull;
+
+struct Timer {
+    generation: u32,
+    deadline: Instant,
+    /// Period in ticks, for periodic timers
+    period: Option<u64>,
+    /// `None` when the slot is free
+    action: Option<Action>,
+}
+
+/// Timers, and their slots sorted by deadline
+struct Queue<const N: usize> {
+    timers: [Timer; N],
+    order: [usize; N],
+    len: usize,
+}
+
+impl<const N: usize> Queue<N> {
+    fn new() -> Self {
+        Queue {
+            timers: core::array::from_fn(|_| Timer {
+                generation: 0,
+                deadline: Instant::from_ticks(0),
+                period: None,
+                action: None,
+            }),
+            order: [0; N],
+            len: 0,
+        }
+    }
+
+    /// Returns the nearest deadline
+    fn next_deadline(&self) -> Option<Instant> {
+        self.order[..self.len]
+            .first()
+            .map(|&slot| self.timers[slot].deadline)
+    }
+
+    /// Take a free slot, and insert its timer by deadline
+    ///
+    /// Timers with equal deadlines expire in the order they were inserted.
+    fn insert(
+        &mut self,
+        deadline: Instant,
+        period: Option<u64>,
+        action: Action,
+    ) -> Result<TimerId, QueueFull> {
+        let slot = self
+            .timers
+            .iter()
+            .position(|timer| timer.action.is_none())
+            .ok_or(QueueFull)?;
+        let timer = &mut self.timers[slot];
+        timer.deadline = deadline;
+        timer.period = period;
+        timer.action = Some(action);
+        let id = TimerId {
+            slot,
+            generation: timer.generation,
+        };
+        self.enqueue(slot);
+        Ok(id)
+    }
+
+    fn enqueue(&mut self, slot: usize) {
+        let deadline = self.timers[slot].deadline;
+        let timers = &self.timers;
+        let position = self.order[..self.len].partition_point(|&s| timers[s].deadline <= deadline);
+        self.order.copy_within(position..self.len, position + 1);
+        self.order[position] = slot;
+        self.len += 1;
+    }
+
+    fn dequeue(&mut self, slot: usize) {
+        if let Some(position) = self.order[..self.len].iter().position(|&s| s == slot) {
+            self.order.copy_within(position + 1..self.len, position);
+            self.len -= 1;
+        }
+    }
+
+    fn is_pending(&self, id: TimerId) -> bool {
+        self.timers
+            .get(id.slot)
+            .is_some_and(|timer| timer.generation == id.generation && timer.action.is_some())
+    }
+
+    /// Remove the timer, and free its slot
+    fn remove(&mut self, id: TimerId) -> Option<Action> {
+        if !self.is_pending(id) {
+            return None;
+        }
+        self.dequeue(id.slot);
+        let timer = &mut self.timers[id.slot];
+        timer.generation = timer.generation.wrapping_add(1);
+        timer.action.take()
+    }
+
+    /// Remove the nearest timer if it expired by `now`
+    ///
+    /// Periodic timers are re-inserted at their next deadline after `now`, and keep
+    /// their IDs.
+    fn pop_expired(&mut self, now: Instant) -> Option<(TimerId, Action)> {
+        let slot = *self.order[..self.len].first()?;
+        let timer = &self.timers[slot];
+        if timer.deadline > now {
+            return None;
+        }
+        let id = TimerId {
+            slot,
+            generation: timer.generation,
+        };
+        match timer.period {
+            Some(period) => {
+                // Skip missed periods, without drifting from the original deadline
+                let late = now.ticks() - timer.deadline.ticks();
+                let periods = late / period + 1;
+                let deadline = timer
+                    .deadline
+                    .ticks()
+                    .saturating_add(periods.saturating_mul(period));
+                let action = timer.action.clone()?;
+                self.dequeue(slot);
+                self.timers[slot].deadline = Instant::from_ticks(deadline);
+                self.enqueue(slot);
+                Some((id, action))
+            }
+            None => self.remove(id).map(|action| (id, action)),
+        }
+    }
+}
+
+/// A service of up to `N` software timers
+///
+/// The service owns a [`Monotonic`](struct.Monotonic.html), and uses its deadline
+/// output compare register. All methods take `&self`, and you may call them from
+/// any context. Call [`on_interrupt`](#method.on_interrupt) from the GPT's interrupt
+/// handler.
+pub struct TimerService<const N: usize> {
+    monotonic: Monotonic,
+    queue: Mutex<RefCell<Queue<N>>>,
+}
+
+impl<const N: usize> TimerService<N> {
+    /// Create a timer service with no timers
+    pub fn new(monotonic: Monotonic) -> Self {
+        TimerService {
+            monotonic,
+            queue: Mutex::new(RefCell::new(Queue::new())),
+        }
+    }
+
+    /// Returns the monotonic
+    pub fn monotonic(&self) -> &Monotonic {
+        &self.monotonic
+    }
+
+    /// Release the monotonic
+    ///
+    /// Pending timers never expire.
+    pub fn release(self) -> Monotonic {
+        self.monotonic
+    }
+
+    /// Start a timer that expires once, `after` from now
+    pub fn start(&self, after: Duration, action: Action) -> Result<TimerId, QueueFull> {
+        self.insert(self.ticks(after), None, action)
+    }
+
+    /// Start a timer that expires every `period`, starting `period` from now
+    ///
+    /// A periodic timer runs until you cancel it. If the service handles an expiration
+    /// late, the timer skips the periods it missed.
+    pub fn start_periodic(&self, period: Duration, action: Action) -> Result<TimerId, QueueFull> {
+        let ticks = self.ticks(period).max(1);
+        self.insert(ticks, Some(ticks), action)
+    }
+
+    /// Returns the ticks in `duration`, rounded up so that timers never expire early
+    fn ticks(&self, duration: Duration) -> u64 {
+        ticks_at_least(duration, self.monotonic.gpt().clock_frequency_hz())
+    }
+
+    fn insert(
+        &self,
+        after: u64,
+        period: Option<u64>,
+        action: Action,
+    ) -> Result<TimerId, QueueFull> {
+        interrupt::free(|cs| {
+            let mut queue = self.queue.borrow(cs).borrow_mut();
+            let deadline = Instant::from_ticks(self.monotonic.now().ticks().saturating_add(after));
+            let id = queue.insert(deadline, period, action)?;
+            if queue.next_deadline() == Some(deadline) {
+                self.monotonic.set_deadline(deadline);
+            }
+            Ok(id)
+        })
+    }
+
+    /// Cancel a timer
+    ///
+    /// Returns `true` if the timer was pending. The service doesn't run a cancelled
+    /// timer's action.
+    pub fn cancel(&self, id: TimerId) -> bool {
+        interrupt::free(|cs| self.queue.borrow(cs).borrow_mut().remove(id).is_some())
+    }
+
+    /// Returns `true` if the timer hasn't expired, and isn't cancelled
+    ///
+    /// Periodic timers are pending until cancelled.
+    pub fn is_pending(&self, id: TimerId) -> bool {
+        interrupt::free(|cs| self.queue.borrow(cs).borrow().is_pending(id))
+    }
+
+    /// Handle the GPT interrupt
+    ///
+    /// Call this from the GPT interrupt handler. This runs the actions of all expired
+    /// timers, then programs the next deadline. Actions run outside of a critical
+    /// section, and they may start or cancel timers.
+    pub fn on_interrupt(&self) {
+        self.monotonic.on_interrupt();
+        if self.monotonic.deadline_reached() {
+            self.monotonic.clear_deadline();
+        }
+        // Bound the work, so that a short periodic timer can't starve the caller
+        for _ in 0..N {
+            let expired = interrupt::free(|cs| {
+                self.queue
+                    .borrow(cs)
+                    .borrow_mut()
+                    .pop_expired(self.monotonic.now())
+            });
+            match expired {
+                Some((id, action)) => action.run(id),
+                None => break,
+            }
+        }
+        interrupt::free(|cs| {
+            if let Some(deadline) = self.queue.borrow(cs).borrow().next_deadline() {
+                self.monotonic.set_deadline(deadline);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Action, Instant, Queue};
+
+    fn callback(_: super::TimerId) {}
+
+    #[test]
+    fn test_queue() {
+        let mut queue: Queue<3> = Queue::new();
+        let at = Instant::from_ticks;
+        let late = queue
+            .insert(at(30), None, Action::Callback(callback))
+            .unwrap();
+        let periodic = queue
+            .insert(at(10), Some(25), Action::Callback(callback))
+            .unwrap();
+        let early = queue
+            .insert(at(20), None, Action::Callback(callback))
+            .unwrap();
+        assert!(queue
+            .insert(at(0), None, Action::Callback(callback))
+            .is_err());
+        assert_eq!(queue.next_deadline(), Some(at(10)));
+
+        assert!(queue.pop_expired(at(9)).is_none());
+        assert_eq!(queue.pop_expired(at(12)).unwrap().0, periodic);
+        assert_eq!(queue.next_deadline(), Some(at(20)));
+
+        assert!(queue.remove(early).is_some());
+        assert!(queue.remove(early).is_none());
+        assert_eq!(queue.pop_expired(at(70)).unwrap().0, late);
+        // The periodic timer skips the deadlines at 35 and 60
+        assert_eq!(queue.pop_expired(at(70)).unwrap().0, periodic);
+        assert_eq!(queue.next_deadline(), Some(at(85)));
+        assert!(queue.is_pending(periodic));
+        assert!(!queue.is_pending(late));
+    }
+}
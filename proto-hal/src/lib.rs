@@ -3,13 +3,13 @@
 #![no_std]
 
 pub mod ccm;
-mod gpio;
-mod gpt;
+pub mod gpio;
+pub mod gpt;
 mod uart;
 pub use proto_ral as ral;
 
-pub use gpio::{Input, Output, GPIO};
-pub use gpt::{OutputCompareRegister, GPT};
+pub use gpio::{Direction, Dynamic, Input, OpenDrain, Output, Trigger, GPIO};
+pub use gpt::{ClockConfig, ClockSource, OutputCompareRegister, RunConfig, GPT};
 pub use uart::UART;
 
 pub mod iomuxc {
@@ -0,0 +1,62 @@
+//! Const register value builders
+//!
+//! `write_reg!` computes a register value from its fields, but only at runtime.
+//! The builders compute the same value in a `const` context.
+
+/// Define a `const` builder for a register's value
+///
+/// Invoke inside a register's field module, and list every field of the
+/// register. The register width defaults to `u32`.
+macro_rules! builder {
+    ( $( $field:ident ),+ $(,)? ) => {
+        builder!(u32; $( $field ),+);
+    };
+    ( $ty:ty; $( $field:ident ),+ $(,)? ) => {
+        /// A `const` builder for this register's value
+        ///
+        /// Start with [`new`](fn.new.html), then set fields by name. Unspecified
+        /// fields are 0. Setting a field to a value that doesn't fit in the field
+        /// panics, which is a compile error in a `const` context.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub struct Builder($ty);
+
+        /// Start building a register value, with every field set to 0
+        #[inline(always)]
+        pub const fn new() -> Builder {
+            Builder(0)
+        }
+
+        impl Builder {
+            /// Start building from an existing register value
+            #[inline(always)]
+            pub const fn from_bits(bits: $ty) -> Self {
+                Builder(bits)
+            }
+
+            /// Returns the register value
+            #[inline(always)]
+            pub const fn bits(self) -> $ty {
+                self.0
+            }
+
+            $(
+                #[doc = concat!("Set the `", stringify!($field), "` field")]
+                #[inline(always)]
+                pub const fn $field(self, value: $ty) -> Self {
+                    assert!(
+                        value <= $field::mask >> $field::offset,
+                        concat!("value does not fit in ", stringify!($field))
+                    );
+                    Builder((self.0 & !$field::mask) | (value << $field::offset))
+                }
+            )+
+        }
+
+        impl From<Builder> for $ty {
+            #[inline(always)]
+            fn from(builder: Builder) -> $ty {
+                builder.0
+            }
+        }
+    };
+}
@@ -47,6 +47,20 @@ pub struct RegisterBlock {
     pub DR_TOGGLE: WORegister<u32>,
 }
 
+assert_layout!(RegisterBlock, size: 0x90, {
+    DR: 0x00,
+    GDIR: 0x04,
+    PSR: 0x08,
+    ICR1: 0x0C,
+    ICR2: 0x10,
+    IMR: 0x14,
+    ISR: 0x18,
+    EDGE_SEL: 0x1C,
+    DR_SET: 0x84,
+    DR_CLEAR: 0x88,
+    DR_TOGGLE: 0x8C,
+});
+
 pub struct ResetValues {
     pub DR: u32,
     pub GDIR: u32,
@@ -62,6 +76,7 @@ pub struct ResetValues {
 }
 pub struct Instance {
     pub(crate) addr: u32,
+    pub(crate) reset: &'static ResetValues,
     pub(crate) _marker: PhantomData<*const RegisterBlock>,
 }
 impl ::core::ops::Deref for Instance {
@@ -73,3 +88,37 @@ impl ::core::ops::Deref for Instance {
 }
 
 unsafe impl Send for Instance {}
+
+/// Restore every writable GPIO register to its reset value
+///
+/// The GPIO has no software reset, so this writes each register. It masks
+/// interrupts first, then restores the interrupt configuration. It turns
+/// outputs into inputs before it restores `DR`, so pins don't glitch. Finally,
+/// it clears every interrupt status flag.
+pub fn reset_all(gpio: &Instance) {
+    let reset = gpio.reset;
+    gpio.IMR.write(reset.IMR);
+    gpio.EDGE_SEL.write(reset.EDGE_SEL);
+    gpio.ICR1.write(reset.ICR1);
+    gpio.ICR2.write(reset.ICR2);
+    gpio.GDIR.write(reset.GDIR);
+    gpio.DR.write(reset.DR);
+    // W1C
+    gpio.ISR.write(u32::MAX);
+}
+
+/// Returns every GPIO register that doesn't hold its reset value
+///
+/// This skips `PSR` and `ISR`, since they follow the pads, and the write-only
+/// `DR_SET`, `DR_CLEAR`, and `DR_TOGGLE`.
+pub fn verify_reset(gpio: &Instance) -> impl Iterator<Item = crate::ResetDifference> {
+    let reset = gpio.reset;
+    crate::reset::differences([
+        ("DR", reset.DR, gpio.DR.read()),
+        ("GDIR", reset.GDIR, gpio.GDIR.read()),
+        ("ICR1", reset.ICR1, gpio.ICR1.read()),
+        ("ICR2", reset.ICR2, gpio.ICR2.read()),
+        ("IMR", reset.IMR, gpio.IMR.read()),
+        ("EDGE_SEL", reset.EDGE_SEL, gpio.EDGE_SEL.read()),
+    ])
+}
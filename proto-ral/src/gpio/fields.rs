@@ -1,6 +1,8 @@
 /// GPIO data register
 pub mod DR {
 
+    builder!(DR);
+
     /// DR
     pub mod DR {
         /// Offset (0 bits)
@@ -19,6 +21,8 @@ pub mod DR {
 /// GPIO direction register
 pub mod GDIR {
 
+    builder!(GDIR);
+
     /// GDIR
     pub mod GDIR {
         /// Offset (0 bits)
@@ -55,6 +59,11 @@ pub mod PSR {
 /// GPIO interrupt configuration register1
 pub mod ICR1 {
 
+    builder!(
+        ICR0, ICR1, ICR2, ICR3, ICR4, ICR5, ICR6, ICR7, ICR8, ICR9, ICR10, ICR11, ICR12, ICR13,
+        ICR14, ICR15,
+    );
+
     /// ICR0
     pub mod ICR0 {
         /// Offset (0 bits)
@@ -281,6 +290,11 @@ pub mod ICR1 {
 /// GPIO interrupt configuration register2
 pub mod ICR2 {
 
+    builder!(
+        ICR16, ICR17, ICR18, ICR19, ICR20, ICR21, ICR22, ICR23, ICR24, ICR25, ICR26, ICR27, ICR28,
+        ICR29, ICR30, ICR31,
+    );
+
     /// ICR16
     pub mod ICR16 {
         /// Offset (0 bits)
@@ -507,6 +521,8 @@ pub mod ICR2 {
 /// GPIO interrupt mask register
 pub mod IMR {
 
+    builder!(IMR);
+
     /// IMR
     pub mod IMR {
         /// Offset (0 bits)
@@ -525,6 +541,8 @@ pub mod IMR {
 /// GPIO interrupt status register
 pub mod ISR {
 
+    builder!(ISR);
+
     /// ISR
     pub mod ISR {
         /// Offset (0 bits)
@@ -543,6 +561,8 @@ pub mod ISR {
 /// GPIO edge select register
 pub mod EDGE_SEL {
 
+    builder!(GPIO_EDGE_SEL);
+
     /// GPIO_EDGE_SEL
     pub mod GPIO_EDGE_SEL {
         /// Offset (0 bits)
@@ -561,6 +581,8 @@ pub mod EDGE_SEL {
 /// GPIO data register SET
 pub mod DR_SET {
 
+    builder!(DR_SET);
+
     /// DR_SET
     pub mod DR_SET {
         /// Offset (0 bits)
@@ -579,6 +601,8 @@ pub mod DR_SET {
 /// GPIO data register CLEAR
 pub mod DR_CLEAR {
 
+    builder!(DR_CLEAR);
+
     /// DR_CLEAR
     pub mod DR_CLEAR {
         /// Offset (0 bits)
@@ -597,6 +621,8 @@ pub mod DR_CLEAR {
 /// GPIO data register TOGGLE
 pub mod DR_TOGGLE {
 
+    builder!(DR_TOGGLE);
+
     /// DR_TOGGLE
     pub mod DR_TOGGLE {
         /// Offset (0 bits)
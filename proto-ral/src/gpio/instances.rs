@@ -7,8 +7,13 @@ pub use crate::gpio::{DR, DR_CLEAR, DR_SET, DR_TOGGLE, EDGE_SEL, GDIR, ICR1, ICR
 pub mod GPIO1 {
     use super::Instance;
     use super::ResetValues;
+
+    /// Base address of GPIO1
+    pub const ADDRESS: u32 = 0x401b8000;
+
     const INSTANCE: Instance = Instance {
-        addr: 0x401b8000,
+        addr: ADDRESS,
+        reset: &reset,
         _marker: ::core::marker::PhantomData,
     };
 
@@ -101,8 +106,17 @@ pub const GPIO1: *const RegisterBlock = 0x401b8000 as *const _;
 pub mod GPIO2 {
     use super::Instance;
     use super::ResetValues;
+
+    /// Base address of GPIO2
+    #[cfg(any(feature = "imxrt1060"))]
+    pub const ADDRESS: u32 = 0x401bc000;
+    /// Base address of GPIO2
+    #[cfg(any(feature = "imxrt1010"))]
+    pub const ADDRESS: u32 = 0x42000000;
+
     const INSTANCE: Instance = Instance {
-        addr: 0x401bc000,
+        addr: ADDRESS,
+        reset: &reset,
         _marker: ::core::marker::PhantomData,
     };
 
@@ -197,8 +211,13 @@ pub const GPIO2: *const RegisterBlock = 0x42000000 as *const _;
 pub mod GPIO3 {
     use super::Instance;
     use super::ResetValues;
+
+    /// Base address of GPIO3
+    pub const ADDRESS: u32 = 0x401c0000;
+
     const INSTANCE: Instance = Instance {
-        addr: 0x401c0000,
+        addr: ADDRESS,
+        reset: &reset,
         _marker: ::core::marker::PhantomData,
     };
 
@@ -291,8 +310,13 @@ pub const GPIO3: *const RegisterBlock = 0x401c0000 as *const _;
 pub mod GPIO4 {
     use super::Instance;
     use super::ResetValues;
+
+    /// Base address of GPIO4
+    pub const ADDRESS: u32 = 0x401c4000;
+
     const INSTANCE: Instance = Instance {
-        addr: 0x401c4000,
+        addr: ADDRESS,
+        reset: &reset,
         _marker: ::core::marker::PhantomData,
     };
 
@@ -385,8 +409,13 @@ pub const GPIO4: *const RegisterBlock = 0x401c4000 as *const _;
 pub mod GPIO5 {
     use super::Instance;
     use super::ResetValues;
+
+    /// Base address of GPIO5
+    pub const ADDRESS: u32 = 0x400c0000;
+
     const INSTANCE: Instance = Instance {
-        addr: 0x400c0000,
+        addr: ADDRESS,
+        reset: &reset,
         _marker: ::core::marker::PhantomData,
     };
 
@@ -479,8 +508,13 @@ pub const GPIO5: *const RegisterBlock = 0x400c0000 as *const _;
 pub mod GPIO6 {
     use super::Instance;
     use super::ResetValues;
+
+    /// Base address of GPIO6
+    pub const ADDRESS: u32 = 0x42000000;
+
     const INSTANCE: Instance = Instance {
-        addr: 0x42000000,
+        addr: ADDRESS,
+        reset: &reset,
         _marker: ::core::marker::PhantomData,
     };
 
@@ -573,8 +607,13 @@ pub const GPIO6: *const RegisterBlock = 0x42000000 as *const _;
 pub mod GPIO7 {
     use super::Instance;
     use super::ResetValues;
+
+    /// Base address of GPIO7
+    pub const ADDRESS: u32 = 0x42004000;
+
     const INSTANCE: Instance = Instance {
-        addr: 0x42004000,
+        addr: ADDRESS,
+        reset: &reset,
         _marker: ::core::marker::PhantomData,
     };
 
@@ -667,8 +706,13 @@ pub const GPIO7: *const RegisterBlock = 0x42004000 as *const _;
 pub mod GPIO8 {
     use super::Instance;
     use super::ResetValues;
+
+    /// Base address of GPIO8
+    pub const ADDRESS: u32 = 0x42008000;
+
     const INSTANCE: Instance = Instance {
-        addr: 0x42008000,
+        addr: ADDRESS,
+        reset: &reset,
         _marker: ::core::marker::PhantomData,
     };
 
@@ -761,8 +805,13 @@ pub const GPIO8: *const RegisterBlock = 0x42008000 as *const _;
 pub mod GPIO9 {
     use super::Instance;
     use super::ResetValues;
+
+    /// Base address of GPIO9
+    pub const ADDRESS: u32 = 0x4200c000;
+
     const INSTANCE: Instance = Instance {
-        addr: 0x4200c000,
+        addr: ADDRESS,
+        reset: &reset,
         _marker: ::core::marker::PhantomData,
     };
 
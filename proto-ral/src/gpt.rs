@@ -23,39 +23,39 @@ pub struct RegisterBlock {
     /// GPT Interrupt Register
     pub IR: RWRegister<u32>,
 
-    /// GPT Output Compare Register 1
-    pub OCR1: RWRegister<u32>,
+    /// GPT Output Compare Registers 1 through 3
+    pub OCR: [RWRegister<u32>; 3],
 
-    /// GPT Output Compare Register 2
-    pub OCR2: RWRegister<u32>,
-
-    /// GPT Output Compare Register 3
-    pub OCR3: RWRegister<u32>,
-
-    /// GPT Input Capture Register 1
-    pub ICR1: RORegister<u32>,
-
-    /// GPT Input Capture Register 2
-    pub ICR2: RORegister<u32>,
+    /// GPT Input Capture Registers 1 and 2
+    pub ICR: [RORegister<u32>; 2],
 
     /// GPT Counter Register
     pub CNT: RORegister<u32>,
 }
+
+assert_layout!(RegisterBlock, size: 0x28, {
+    CR: 0x00,
+    PR: 0x04,
+    SR: 0x08,
+    IR: 0x0C,
+    OCR: 0x10,
+    ICR: 0x1C,
+    CNT: 0x24,
+});
+
 pub struct ResetValues {
     pub CR: u32,
     pub PR: u32,
     pub SR: u32,
     pub IR: u32,
-    pub OCR1: u32,
-    pub OCR2: u32,
-    pub OCR3: u32,
-    pub ICR1: u32,
-    pub ICR2: u32,
+    pub OCR: [u32; 3],
+    pub ICR: [u32; 2],
     pub CNT: u32,
 }
 
 pub struct Instance {
     pub(crate) addr: u32,
+    pub(crate) reset: &'static ResetValues,
     pub(crate) _marker: PhantomData<*const RegisterBlock>,
 }
 
@@ -68,3 +68,34 @@ impl ::core::ops::Deref for Instance {
 }
 
 unsafe impl Send for Instance {}
+
+/// Restore every writable GPT register to its reset value
+///
+/// This uses the GPT's software reset, `CR[SWR]`. The software reset doesn't
+/// touch the `EN`, `ENMOD`, `STOPEN`, `DOZEEN`, `WAITEN`, and `DBGEN` bits of
+/// `CR`, so those are written afterwards. The timer is disabled, and its
+/// interrupts masked, before the reset.
+pub fn reset_all(gpt: &Instance) {
+    gpt.IR.write(0);
+    gpt.CR.write(gpt.CR.read() & !CR::EN::mask);
+    gpt.CR.write(gpt.CR.read() | CR::SWR::mask);
+    while gpt.CR.read() & CR::SWR::mask != 0 {}
+    gpt.CR.write(gpt.reset.CR);
+}
+
+/// Returns every GPT register that doesn't hold its reset value
+pub fn verify_reset(gpt: &Instance) -> impl Iterator<Item = crate::ResetDifference> {
+    let reset = gpt.reset;
+    crate::reset::differences([
+        ("CR", reset.CR, gpt.CR.read()),
+        ("PR", reset.PR, gpt.PR.read()),
+        ("SR", reset.SR, gpt.SR.read()),
+        ("IR", reset.IR, gpt.IR.read()),
+        ("OCR[0]", reset.OCR[0], gpt.OCR[0].read()),
+        ("OCR[1]", reset.OCR[1], gpt.OCR[1].read()),
+        ("OCR[2]", reset.OCR[2], gpt.OCR[2].read()),
+        ("ICR[0]", reset.ICR[0], gpt.ICR[0].read()),
+        ("ICR[1]", reset.ICR[1], gpt.ICR[1].read()),
+        ("CNT", reset.CNT, gpt.CNT.read()),
+    ])
+}
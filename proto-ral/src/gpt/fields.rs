@@ -1,6 +1,11 @@
 /// GPT Control Register
 pub mod CR {
 
+    builder!(
+        EN, ENMOD, DBGEN, WAITEN, DOZEEN, STOPEN, CLKSRC, FRR, EN_24M, SWR, IM1, IM2, OM1, OM2,
+        OM3, FO1, FO2, FO3,
+    );
+
     /// GPT Enable
     pub mod EN {
         /// Offset (0 bits)
@@ -317,8 +322,8 @@ pub mod CR {
             /// 0b011: Set output pin
             pub const OM3_3: u32 = 0b011;
 
-            /// 0b000: Generate an active low pulse (that is one input clock wide) on the output pin.
-            pub const OM3_4: u32 = 0b000;
+            /// 0b100: Generate an active low pulse (that is one input clock wide) on the output pin.
+            pub const OM3_4: u32 = 0b100;
         }
     }
 
@@ -375,6 +380,8 @@ pub mod CR {
 /// GPT Prescaler Register
 pub mod PR {
 
+    builder!(PRESCALER, PRESCALER24M);
+
     /// Prescaler bits
     pub mod PRESCALER {
         /// Offset (0 bits)
@@ -427,6 +434,8 @@ pub mod PR {
 /// GPT Status Register
 pub mod SR {
 
+    builder!(OF1, OF2, OF3, IF1, IF2, ROV);
+
     /// See OF3
     pub mod OF1 {
         /// Offset (0 bits)
@@ -536,6 +545,8 @@ pub mod SR {
 /// GPT Interrupt Register
 pub mod IR {
 
+    builder!(OF1IE, OF2IE, OF3IE, IF1IE, IF2IE, ROVIE);
+
     /// See OF3IE
     pub mod OF1IE {
         /// Offset (0 bits)
@@ -642,8 +653,10 @@ pub mod IR {
     }
 }
 
-/// GPT Output Compare Register 1
-pub mod OCR1 {
+/// GPT Output Compare Registers
+pub mod OCR {
+
+    builder!(COMP);
 
     /// Compare Value
     pub mod COMP {
@@ -660,18 +673,8 @@ pub mod OCR1 {
     }
 }
 
-/// GPT Output Compare Register 2
-pub mod OCR2 {
-    pub use super::OCR1::COMP;
-}
-
-/// GPT Output Compare Register 3
-pub mod OCR3 {
-    pub use super::OCR1::COMP;
-}
-
-/// GPT Input Capture Register 1
-pub mod ICR1 {
+/// GPT Input Capture Registers
+pub mod ICR {
 
     /// Capture Value
     pub mod CAPT {
@@ -688,11 +691,6 @@ pub mod ICR1 {
     }
 }
 
-/// GPT Input Capture Register 2
-pub mod ICR2 {
-    pub use super::ICR1::CAPT;
-}
-
 /// GPT Counter Register
 pub mod CNT {
 
@@ -2,7 +2,7 @@
 
 pub use crate::gpt::Instance;
 pub use crate::gpt::{RegisterBlock, ResetValues};
-pub use crate::gpt::{CNT, CR, ICR1, ICR2, IR, OCR1, OCR2, OCR3, PR, SR};
+pub use crate::gpt::{CNT, CR, ICR, IR, OCR, PR, SR};
 
 /// Access functions for the GPT1 peripheral instance
 #[cfg(any(feature = "imxrt1010", feature = "imxrt1060"))]
@@ -11,8 +11,12 @@ pub mod GPT1 {
 
     use super::Instance;
 
+    /// Base address of GPT1
+    pub const ADDRESS: u32 = 0x401ec000;
+
     const INSTANCE: Instance = Instance {
-        addr: 0x401ec000,
+        addr: ADDRESS,
+        reset: &reset,
         _marker: ::core::marker::PhantomData,
     };
 
@@ -22,11 +26,8 @@ pub mod GPT1 {
         PR: 0x00000000,
         SR: 0x00000000,
         IR: 0x00000000,
-        OCR1: 0xFFFFFFFF,
-        OCR2: 0xFFFFFFFF,
-        OCR3: 0xFFFFFFFF,
-        ICR1: 0x00000000,
-        ICR2: 0x00000000,
+        OCR: [0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF],
+        ICR: [0x00000000, 0x00000000],
         CNT: 0x00000000,
     };
 
@@ -107,8 +108,12 @@ pub mod GPT2 {
 
     use super::Instance;
 
+    /// Base address of GPT2
+    pub const ADDRESS: u32 = 0x401f0000;
+
     const INSTANCE: Instance = Instance {
-        addr: 0x401f0000,
+        addr: ADDRESS,
+        reset: &reset,
         _marker: ::core::marker::PhantomData,
     };
 
@@ -118,11 +123,8 @@ pub mod GPT2 {
         PR: 0x00000000,
         SR: 0x00000000,
         IR: 0x00000000,
-        OCR1: 0xFFFFFFFF,
-        OCR2: 0xFFFFFFFF,
-        OCR3: 0xFFFFFFFF,
-        ICR1: 0x00000000,
-        ICR2: 0x00000000,
+        OCR: [0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF],
+        ICR: [0x00000000, 0x00000000],
         CNT: 0x00000000,
     };
 
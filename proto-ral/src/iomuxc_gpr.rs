@@ -0,0 +1,83 @@
+//! IOMUXC_GPR
+//!
+//! Only the general purpose registers that select the GPIO banks are described.
+
+mod fields;
+pub use fields::*;
+
+#[cfg(any(feature = "imxrt1060"))]
+mod instances;
+#[cfg(any(feature = "imxrt1060"))]
+pub use instances::*;
+
+use crate::RWRegister;
+use core::marker::PhantomData;
+
+#[repr(C)]
+pub struct RegisterBlock {
+    _reserved1: [u32; 26],
+
+    /// GPR26 General Purpose Register
+    pub GPR26: RWRegister<u32>,
+
+    /// GPR27 General Purpose Register
+    pub GPR27: RWRegister<u32>,
+
+    /// GPR28 General Purpose Register
+    pub GPR28: RWRegister<u32>,
+
+    /// GPR29 General Purpose Register
+    pub GPR29: RWRegister<u32>,
+}
+
+assert_layout!(RegisterBlock, size: 0x78, {
+    GPR26: 0x68,
+    GPR27: 0x6C,
+    GPR28: 0x70,
+    GPR29: 0x74,
+});
+
+pub struct ResetValues {
+    pub GPR26: u32,
+    pub GPR27: u32,
+    pub GPR28: u32,
+    pub GPR29: u32,
+}
+
+pub struct Instance {
+    pub(crate) addr: u32,
+    pub(crate) reset: &'static ResetValues,
+    pub(crate) _marker: PhantomData<*const RegisterBlock>,
+}
+
+impl ::core::ops::Deref for Instance {
+    type Target = RegisterBlock;
+    #[inline(always)]
+    fn deref(&self) -> &RegisterBlock {
+        unsafe { &*(self.addr as *const _) }
+    }
+}
+
+unsafe impl Send for Instance {}
+
+/// Restore every described IOMUXC_GPR register to its reset value
+///
+/// This routes every pin back to its standard GPIO bank.
+pub fn reset_all(gpr: &Instance) {
+    let reset = gpr.reset;
+    gpr.GPR26.write(reset.GPR26);
+    gpr.GPR27.write(reset.GPR27);
+    gpr.GPR28.write(reset.GPR28);
+    gpr.GPR29.write(reset.GPR29);
+}
+
+/// Returns every described IOMUXC_GPR register that doesn't hold its reset value
+pub fn verify_reset(gpr: &Instance) -> impl Iterator<Item = crate::ResetDifference> {
+    let reset = gpr.reset;
+    crate::reset::differences([
+        ("GPR26", reset.GPR26, gpr.GPR26.read()),
+        ("GPR27", reset.GPR27, gpr.GPR27.read()),
+        ("GPR28", reset.GPR28, gpr.GPR28.read()),
+        ("GPR29", reset.GPR29, gpr.GPR29.read()),
+    ])
+}
@@ -0,0 +1,79 @@
+/// GPR26 General Purpose Register
+pub mod GPR26 {
+
+    builder!(GPIO_MUX1_GPIO_SEL);
+
+    /// Selects GPIO1 (0) or GPIO6 (1) for each pin
+    pub mod GPIO_MUX1_GPIO_SEL {
+        /// Offset (0 bits)
+        pub const offset: u32 = 0;
+        /// Mask (32 bits: 0xffffffff << 0)
+        pub const mask: u32 = 0xffffffff << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+}
+
+/// GPR27 General Purpose Register
+pub mod GPR27 {
+
+    builder!(GPIO_MUX2_GPIO_SEL);
+
+    /// Selects GPIO2 (0) or GPIO7 (1) for each pin
+    pub mod GPIO_MUX2_GPIO_SEL {
+        /// Offset (0 bits)
+        pub const offset: u32 = 0;
+        /// Mask (32 bits: 0xffffffff << 0)
+        pub const mask: u32 = 0xffffffff << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+}
+
+/// GPR28 General Purpose Register
+pub mod GPR28 {
+
+    builder!(GPIO_MUX3_GPIO_SEL);
+
+    /// Selects GPIO3 (0) or GPIO8 (1) for each pin
+    pub mod GPIO_MUX3_GPIO_SEL {
+        /// Offset (0 bits)
+        pub const offset: u32 = 0;
+        /// Mask (32 bits: 0xffffffff << 0)
+        pub const mask: u32 = 0xffffffff << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+}
+
+/// GPR29 General Purpose Register
+pub mod GPR29 {
+
+    builder!(GPIO_MUX4_GPIO_SEL);
+
+    /// Selects GPIO4 (0) or GPIO9 (1) for each pin
+    pub mod GPIO_MUX4_GPIO_SEL {
+        /// Offset (0 bits)
+        pub const offset: u32 = 0;
+        /// Mask (32 bits: 0xffffffff << 0)
+        pub const mask: u32 = 0xffffffff << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+}
@@ -0,0 +1,99 @@
+//! IOMUXC_GPR
+
+pub use crate::iomuxc_gpr::Instance;
+pub use crate::iomuxc_gpr::{RegisterBlock, ResetValues};
+pub use crate::iomuxc_gpr::{GPR26, GPR27, GPR28, GPR29};
+
+/// Access functions for the IOMUXC_GPR peripheral instance
+#[cfg(any(feature = "imxrt1060"))]
+pub mod IOMUXC_GPR {
+    use super::ResetValues;
+
+    use super::Instance;
+
+    /// Base address of IOMUXC_GPR
+    pub const ADDRESS: u32 = 0x400ac000;
+
+    const INSTANCE: Instance = Instance {
+        addr: ADDRESS,
+        reset: &reset,
+        _marker: ::core::marker::PhantomData,
+    };
+
+    /// Reset values for each field in IOMUXC_GPR
+    pub const reset: ResetValues = ResetValues {
+        GPR26: 0x00000000,
+        GPR27: 0x00000000,
+        GPR28: 0x00000000,
+        GPR29: 0x00000000,
+    };
+
+    #[allow(renamed_and_removed_lints)]
+    #[allow(private_no_mangle_statics)]
+    #[no_mangle]
+    static mut IOMUXC_GPR_TAKEN: bool = false;
+
+    /// Safe access to IOMUXC_GPR
+    ///
+    /// This function returns `Some(Instance)` if this instance is not
+    /// currently taken, and `None` if it is. This ensures that if you
+    /// do get `Some(Instance)`, you are ensured unique access to
+    /// the peripheral and there cannot be data races (unless other
+    /// code uses `unsafe`, of course). You can then pass the
+    /// `Instance` around to other functions as required. When you're
+    /// done with it, you can call `release(instance)` to return it.
+    ///
+    /// `Instance` itself dereferences to a `RegisterBlock`, which
+    /// provides access to the peripheral's registers.    
+    #[inline]
+    pub fn take() -> Option<Instance> {
+        cortex_m::interrupt::free(|_| unsafe {
+            if IOMUXC_GPR_TAKEN {
+                None
+            } else {
+                IOMUXC_GPR_TAKEN = true;
+                Some(INSTANCE)
+            }
+        })
+    }
+
+    /// Release exclusive access to IOMUXC_GPR
+    ///
+    /// This function allows you to return an `Instance` so that it
+    /// is available to `take()` again. This function will panic if
+    /// you return a different `Instance` or if this instance is not
+    /// already taken.    
+    #[inline]
+    pub fn release(inst: Instance) {
+        cortex_m::interrupt::free(|_| unsafe {
+            if IOMUXC_GPR_TAKEN && inst.addr == INSTANCE.addr {
+                IOMUXC_GPR_TAKEN = false;
+            } else {
+                panic!("Released a peripheral which was not taken");
+            }
+        });
+    }
+
+    /// Unsafely steal IOMUXC_GPR
+    ///
+    /// This function is similar to take() but forcibly takes the
+    /// Instance, marking it as taken irregardless of its previous
+    /// state.    
+    #[inline]
+    pub unsafe fn steal() -> Instance {
+        IOMUXC_GPR_TAKEN = true;
+        INSTANCE
+    }
+}
+
+/// Raw pointer to IOMUXC_GPR
+///
+/// Dereferencing this is unsafe because you are not ensured unique
+/// access to the peripheral, so you may encounter data races with
+/// other users of this peripheral. It is up to you to ensure you
+/// will not cause data races.
+///
+/// This constant is provided for ease of use in unsafe code: you can
+/// simply call for example `write_reg!(gpio, GPIOA, ODR, 1);`.
+#[cfg(any(feature = "imxrt1060"))]
+pub const IOMUXC_GPR: *const RegisterBlock = 0x400ac000 as *const _;
@@ -0,0 +1,112 @@
+//! Compile-time layout checks
+//!
+//! Register blocks are `#[repr(C)]` structs with hand-counted padding. A
+//! miscounted reserved array shifts every register that follows it, and
+//! nothing would complain until we touched the hardware. The checks in
+//! this module fail the build instead.
+//!
+//! Each peripheral module describes its register offsets with
+//! `assert_layout!`, right next to its `RegisterBlock`. Each peripheral
+//! instance must also be listed in the chip's `INSTANCES` table, below.
+//! When you add a peripheral, add it in both places.
+
+/// Assert the size of a `RegisterBlock`, and the byte offset of each register
+///
+/// Offsets are the ones documented in the reference manual. The block fails to
+/// compile if any register isn't where the manual says it should be.
+macro_rules! assert_layout {
+    ($block:ty, size: $size:expr, { $( $reg:ident : $offset:expr ),+ $(,)? }) => {
+        const _: [(); 1] = [(); (::core::mem::size_of::<$block>() == $size) as usize];
+        $(
+            const _: [(); 1] = [(); (::core::mem::offset_of!($block, $reg) == $offset) as usize];
+        )+
+    };
+}
+
+/// Describes a peripheral instance's base address, and the size of its register block
+#[cfg(any(feature = "imxrt1010", feature = "imxrt1060"))]
+type Span = (u32, usize);
+
+#[cfg(any(feature = "imxrt1010", feature = "imxrt1060"))]
+macro_rules! span {
+    ($periph:ident :: $instance:ident) => {
+        (
+            crate::$periph::$instance::ADDRESS,
+            ::core::mem::size_of::<crate::$periph::RegisterBlock>(),
+        )
+    };
+}
+
+#[cfg(feature = "imxrt1010")]
+const INSTANCES: &[Span] = &[
+    span!(gpio::GPIO1),
+    span!(gpio::GPIO2),
+    span!(gpio::GPIO5),
+    span!(gpt::GPT1),
+    span!(gpt::GPT2),
+    span!(lpuart::LPUART1),
+    span!(lpuart::LPUART2),
+    span!(lpuart::LPUART3),
+    span!(lpuart::LPUART4),
+];
+
+#[cfg(feature = "imxrt1060")]
+const INSTANCES: &[Span] = &[
+    span!(gpio::GPIO1),
+    span!(gpio::GPIO2),
+    span!(gpio::GPIO3),
+    span!(gpio::GPIO4),
+    span!(gpio::GPIO5),
+    span!(gpio::GPIO6),
+    span!(gpio::GPIO7),
+    span!(gpio::GPIO8),
+    span!(gpio::GPIO9),
+    span!(gpt::GPT1),
+    span!(gpt::GPT2),
+    span!(iomuxc_gpr::IOMUXC_GPR),
+    span!(lpuart::LPUART1),
+    span!(lpuart::LPUART2),
+    span!(lpuart::LPUART3),
+    span!(lpuart::LPUART4),
+    span!(lpuart::LPUART5),
+    span!(lpuart::LPUART6),
+    span!(lpuart::LPUART7),
+    span!(lpuart::LPUART8),
+];
+
+#[cfg(any(feature = "imxrt1010", feature = "imxrt1060"))]
+const _: [(); 1] = [(); instances_are_valid(INSTANCES) as usize];
+
+/// Start of the Cortex-M peripheral address space
+#[cfg(any(feature = "imxrt1010", feature = "imxrt1060"))]
+const PERIPHERAL_START: u64 = 0x4000_0000;
+/// End (exclusive) of the Cortex-M peripheral address space
+#[cfg(any(feature = "imxrt1010", feature = "imxrt1060"))]
+const PERIPHERAL_END: u64 = 0x6000_0000;
+
+/// Returns `true` if every register block lies in the peripheral address space,
+/// and no two register blocks overlap
+///
+/// Non-overlapping blocks also have unique addresses.
+#[cfg(any(feature = "imxrt1010", feature = "imxrt1060"))]
+const fn instances_are_valid(spans: &[Span]) -> bool {
+    let mut i = 0;
+    while i < spans.len() {
+        let (start, size) = spans[i];
+        let (start, end) = (start as u64, start as u64 + size as u64);
+        if start < PERIPHERAL_START || end > PERIPHERAL_END {
+            return false;
+        }
+        let mut j = i + 1;
+        while j < spans.len() {
+            let (other, other_size) = spans[j];
+            let (other, other_end) = (other as u64, other as u64 + other_size as u64);
+            if start < other_end && other < end {
+                return false;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    true
+}
@@ -1,10 +1,67 @@
+//! Prototype RAL for the i.MX RT processor family
+//!
+//! # Register widths
+//!
+//! Most registers are 32 bits wide, so they're `RWRegister<u32>`, `RORegister<u32>`, and
+//! friends. Registers that are 8 or 16 bits wide, or that need narrow loads and stores, use
+//! `u8` or `u16` instead. Everything that describes such a register takes its width:
+//!
+//! - the `mask` of each field,
+//! - the named values in the field's `R`, `W`, and `RW` modules,
+//! - the register's member in `ResetValues`.
+//!
+//! Field `offset`s are always `u32`. The `read_reg!`, `write_reg!`, `modify_reg!`, and
+//! `reset_reg!` macros work the same for every width, and they read and write values of the
+//! register's type.
+//!
+//! # Const register values
+//!
+//! The field module of each writable register has a `const` builder. Use it to compute a
+//! register value in a `const` context, like a static configuration table. The builder
+//! shares its offsets and masks with the `*_reg!` macros.
+//!
+//! ```
+//! use proto_ral::gpt::CR::{self, CLKSRC::RW::CLKSRC_5};
+//!
+//! const CONFIG: u32 = CR::new().EN(1).CLKSRC(CLKSRC_5).bits();
+//! # assert_eq!(CONFIG, 0x141);
+//! ```
+//!
+//! # Resetting peripherals
+//!
+//! Each peripheral module has a `reset_all` function, which restores an instance's writable
+//! registers to their reset values, and a `verify_reset` function, which reports the registers
+//! that don't hold their reset values.
+//!
+//! ```no_run
+//! # #[cfg(feature = "imxrt1060")] {
+//! use proto_ral::gpt;
+//!
+//! let gpt = gpt::GPT1::take().unwrap();
+//! gpt::reset_all(&gpt);
+//! assert_eq!(gpt::verify_reset(&gpt).count(), 0);
+//! # }
+//! ```
+
 #![no_std]
 #![allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
 
+#[macro_use]
+mod builder;
+#[macro_use]
+mod layout;
+mod reset;
+
 pub mod gpio;
 pub mod gpt;
+pub mod iomuxc_gpr;
 pub mod lpuart;
 
+pub use reset::ResetDifference;
+
+#[cfg(test)]
+mod tests;
+
 #[cfg(feature = "rt")]
 mod runtime;
 #[cfg(feature = "rt")]
@@ -188,6 +245,23 @@ impl<T: Copy> UnsafeWORegister<T> {
 ///   `*const RegisterBlock`),
 /// * the register you wish you access: `ICR1` (a field on the `RegisterBlock`).
 ///
+/// Registers that repeat are arrays in the `RegisterBlock`. Select one with an index, like
+/// `OCR[n]`. Groups of registers that repeat together, like the transfer control descriptors
+/// of a DMA channel, are clusters. A cluster is a module, like `TCD`, with its own
+/// `RegisterBlock`, and the peripheral's `RegisterBlock` holds an array of them. Select a
+/// register in a cluster with an index and a name, like `TCD[n].CSR`. Every element of an array
+/// shares the same fields, found in the `OCR` or `TCD::CSR` module of the peripheral:
+/// ```rust,no_run
+/// # use imxrt_ral::{read_reg, write_reg, modify_reg, reset_reg}; fn main() {
+/// # let gpt = imxrt_ral::gpt::GPT1::take().unwrap();
+/// // Set the second output compare register.
+/// write_reg!(imxrt_ral::gpt, gpt, OCR[1], 1000);
+/// // Set the third, using the field.
+/// write_reg!(imxrt_ral::gpt, gpt, OCR[2], COMP: 2000);
+/// # }
+/// ```
+/// Indexing out of bounds panics.
+///
 /// In the single-value usage, the final argument is just the value to write:
 /// ```rust,no_run
 /// # use imxrt_ral::{read_reg, write_reg, modify_reg, reset_reg}; fn main() {
@@ -271,6 +345,32 @@ macro_rules! write_reg {
         use $periph::{*};
         (*$instance).$reg.write($value);
     }};
+    ( $periph:path, $instance:expr, $reg:ident [ $idx:expr ], $( $field:ident : $value:expr ),+ ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        #[allow(unused_imports)]
+        (*$instance).$reg[$idx].write(
+            $({ use $periph::{$reg::$field::{mask, offset, W::*, RW::*}}; ($value << offset) & mask }) | *
+        );
+    }};
+    ( $periph:path, $instance:expr, $reg:ident [ $idx:expr ], $value:expr ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        (*$instance).$reg[$idx].write($value);
+    }};
+    ( $periph:path, $instance:expr, $cluster:ident [ $idx:expr ] . $reg:ident, $( $field:ident : $value:expr ),+ ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        #[allow(unused_imports)]
+        (*$instance).$cluster[$idx].$reg.write(
+            $({ use $periph::{$cluster::$reg::$field::{mask, offset, W::*, RW::*}}; ($value << offset) & mask }) | *
+        );
+    }};
+    ( $periph:path, $instance:expr, $cluster:ident [ $idx:expr ] . $reg:ident, $value:expr ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        (*$instance).$cluster[$idx].$reg.write($value);
+    }};
 }
 
 /// Modify a RWRegister or UnsafeRWRegister.
@@ -303,6 +403,9 @@ macro_rules! write_reg {
 ///   `*const RegisterBlock`),
 /// * the register you wish you access: `ICR1` (a field on the `RegisterBlock`).
 ///
+/// As with `write_reg!`, the register may be an element of a register array, like `OCR[n]`,
+/// or a register in a cluster, like `TCD[n].CSR`.
+///
 /// In the whole-register usage, the final argument is a closure that accepts the current value
 /// of the register and returns the new value to write:
 /// ```rust,no_run
@@ -400,6 +503,36 @@ macro_rules! modify_reg {
         use $periph::{*};
         (*$instance).$reg.write($fn((*$instance).$reg.read()));
     }};
+    ( $periph:path, $instance:expr, $reg:ident [ $idx:expr ], $( $field:ident : $value:expr ),+ ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        let reg = &(*$instance).$reg[$idx];
+        #[allow(unused_imports)]
+        reg.write(
+            (reg.read() & !( $({ use $periph::{$reg::$field::mask}; mask }) | * ))
+            | $({ use $periph::{$reg::$field::{mask, offset, W::*, RW::*}}; ($value << offset) & mask }) | *);
+    }};
+    ( $periph:path, $instance:expr, $reg:ident [ $idx:expr ], $fn:expr ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        let reg = &(*$instance).$reg[$idx];
+        reg.write($fn(reg.read()));
+    }};
+    ( $periph:path, $instance:expr, $cluster:ident [ $idx:expr ] . $reg:ident, $( $field:ident : $value:expr ),+ ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        let reg = &(*$instance).$cluster[$idx].$reg;
+        #[allow(unused_imports)]
+        reg.write(
+            (reg.read() & !( $({ use $periph::{$cluster::$reg::$field::mask}; mask }) | * ))
+            | $({ use $periph::{$cluster::$reg::$field::{mask, offset, W::*, RW::*}}; ($value << offset) & mask }) | *);
+    }};
+    ( $periph:path, $instance:expr, $cluster:ident [ $idx:expr ] . $reg:ident, $fn:expr ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        let reg = &(*$instance).$cluster[$idx].$reg;
+        reg.write($fn(reg.read()));
+    }};
 }
 
 /// Read the value from a RORegister, RWRegister, UnsafeRORegister, or UnsafeRWRegister.
@@ -438,6 +571,9 @@ macro_rules! modify_reg {
 ///   `*const RegisterBlock`),
 /// * the register you wish to access: `ICR2` (a field on the `RegisterBlock`).
 ///
+/// As with `write_reg!`, the register may be an element of a register array, like `OCR[n]`,
+/// or a register in a cluster, like `TCD[n].CSR`.
+///
 /// In the whole-register usage, the macro simply returns the register's value:
 /// ```rust,no_run
 /// # use imxrt_ral::{read_reg, write_reg, modify_reg, reset_reg}; fn main() {
@@ -515,6 +651,50 @@ macro_rules! read_reg {
         use $periph::{*};
         ((*$instance).$reg.read())
     }};
+    ( $periph:path, $instance:expr, $reg:ident [ $idx:expr ], $( $field:ident ),+ ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        let val = ((*$instance).$reg[$idx].read());
+        ( $({
+            #[allow(unused_imports)]
+            use $periph::{$reg::$field::{mask, offset, R::*, RW::*}};
+            (val & mask) >> offset
+        }) , *)
+    }};
+    ( $periph:path, $instance:expr, $reg:ident [ $idx:expr ], $field:ident $($cmp:tt)* ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        #[allow(unused_imports)]
+        use $periph::{$reg::$field::{mask, offset, R::*, RW::*}};
+        (((*$instance).$reg[$idx].read() & mask) >> offset) $($cmp)*
+    }};
+    ( $periph:path, $instance:expr, $reg:ident [ $idx:expr ] ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        ((*$instance).$reg[$idx].read())
+    }};
+    ( $periph:path, $instance:expr, $cluster:ident [ $idx:expr ] . $reg:ident, $( $field:ident ),+ ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        let val = ((*$instance).$cluster[$idx].$reg.read());
+        ( $({
+            #[allow(unused_imports)]
+            use $periph::{$cluster::$reg::$field::{mask, offset, R::*, RW::*}};
+            (val & mask) >> offset
+        }) , *)
+    }};
+    ( $periph:path, $instance:expr, $cluster:ident [ $idx:expr ] . $reg:ident, $field:ident $($cmp:tt)* ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        #[allow(unused_imports)]
+        use $periph::{$cluster::$reg::$field::{mask, offset, R::*, RW::*}};
+        (((*$instance).$cluster[$idx].$reg.read() & mask) >> offset) $($cmp)*
+    }};
+    ( $periph:path, $instance:expr, $cluster:ident [ $idx:expr ] . $reg:ident ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        ((*$instance).$cluster[$idx].$reg.read())
+    }};
 }
 
 /// Reset a RWRegister, UnsafeRWRegister, WORegister, or UnsafeWORegister to its reset value.
@@ -546,6 +726,9 @@ macro_rules! read_reg {
 /// * the module for the instance of that peripheral: `GPIO1`,
 /// * the register you wish to access: `ICR1` (a field on the `RegisterBlock`).
 ///
+/// As with `write_reg!`, the register may be an element of a register array, like `OCR[n]`,
+/// or a register in a cluster, like `TCD[n].CSR`.
+///
 /// In the whole-register usage, that's it:
 /// ```rust,no_run
 /// # use imxrt_ral::{read_reg, write_reg, modify_reg, reset_reg}; fn main() {
@@ -596,7 +779,7 @@ macro_rules! reset_reg {
         use $periph::{$instancemod::{reset}};
         #[allow(unused_imports)]
         (*$instance).$reg.write({
-            let resetmask: u32 = $({ use $periph::{$reg::$field::mask}; mask }) | *;
+            let resetmask = $({ use $periph::{$reg::$field::mask}; mask }) | *;
             ((*$instance).$reg.read() & !resetmask) | (reset.$reg & resetmask)
         });
     }};
@@ -606,4 +789,159 @@ macro_rules! reset_reg {
         use $periph::{$instancemod::{reset}};
         (*$instance).$reg.write(reset.$reg);
     }};
+    ( $periph:path, $instance:expr, $instancemod:path, $reg:ident [ $idx:expr ], $( $field:ident ),+ ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        use $periph::{$instancemod::{reset}};
+        let idx = $idx;
+        let reg = &(*$instance).$reg[idx];
+        #[allow(unused_imports)]
+        reg.write({
+            let resetmask = $({ use $periph::{$reg::$field::mask}; mask }) | *;
+            (reg.read() & !resetmask) | (reset.$reg[idx] & resetmask)
+        });
+    }};
+    ( $periph:path, $instance:expr, $instancemod:path, $reg:ident [ $idx:expr ] ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        use $periph::{$instancemod::{reset}};
+        let idx = $idx;
+        (*$instance).$reg[idx].write(reset.$reg[idx]);
+    }};
+    ( $periph:path, $instance:expr, $instancemod:path, $cluster:ident [ $idx:expr ] . $reg:ident, $( $field:ident ),+ ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        use $periph::{$instancemod::{reset}};
+        let idx = $idx;
+        let reg = &(*$instance).$cluster[idx].$reg;
+        #[allow(unused_imports)]
+        reg.write({
+            let resetmask = $({ use $periph::{$cluster::$reg::$field::mask}; mask }) | *;
+            (reg.read() & !resetmask) | (reset.$cluster[idx].$reg & resetmask)
+        });
+    }};
+    ( $periph:path, $instance:expr, $instancemod:path, $cluster:ident [ $idx:expr ] . $reg:ident ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        use $periph::{$instancemod::{reset}};
+        let idx = $idx;
+        (*$instance).$cluster[idx].$reg.write(reset.$cluster[idx].$reg);
+    }};
+}
+
+/// Compute the byte offset of a register from the start of its `RegisterBlock`.
+///
+/// # Examples
+/// ```rust,no_run
+/// # use imxrt_ral::offset_of; fn main() {
+/// // Offset of a register.
+/// const DATA: u32 = offset_of!(imxrt_ral::lpuart, DATA);
+///
+/// // Offset of an element in a register array.
+/// const OCR3: u32 = offset_of!(imxrt_ral::gpt, OCR[2]);
+///
+/// // The address of a register in a known instance, in a const context.
+/// const LPUART2_DATA: u32 = imxrt_ral::lpuart::LPUART2::ADDRESS + DATA;
+/// # }
+/// ```
+///
+/// # Usage
+/// The first argument is the path to the peripheral module: `imxrt_ral::lpuart`. The second
+/// argument is the register, which may be an element of a register array, like `OCR[n]`, or a
+/// register in a cluster, like `TCD[n].CSR`.
+///
+/// The offset is a `u32`, and it's computed in a const context when the index is a constant.
+/// Indexing out of bounds fails to compile when the index is a constant, and panics otherwise.
+/// Add the offset to an instance's `ADDRESS` to find the register's address without an `Instance`.
+/// Use [`addr_of!`](macro.addr_of.html) to find the address of a register in an `Instance`.
+#[macro_export]
+macro_rules! offset_of {
+    ( $periph:path, $reg:ident ) => {{
+        use $periph::*;
+        ::core::mem::offset_of!(RegisterBlock, $reg) as u32
+    }};
+    ( $periph:path, $reg:ident [ $idx:expr ] ) => {{
+        use $periph::*;
+        (::core::mem::offset_of!(RegisterBlock, $reg) as u32)
+            + $crate::element_offset(
+                |block: *const RegisterBlock| unsafe { ::core::ptr::addr_of!((*block).$reg) },
+                $idx,
+            )
+    }};
+    ( $periph:path, $cluster:ident [ $idx:expr ] . $reg:ident ) => {{
+        use $periph::*;
+        (::core::mem::offset_of!(RegisterBlock, $cluster) as u32)
+            + $crate::element_offset(
+                |block: *const RegisterBlock| unsafe { ::core::ptr::addr_of!((*block).$cluster) },
+                $idx,
+            )
+            + (::core::mem::offset_of!($cluster::RegisterBlock, $reg) as u32)
+    }};
+}
+
+/// Returns the offset of element `idx` in the register array that `_array` selects
+///
+/// Implementation detail of `offset_of!`. The function is never called; it only
+/// conveys the element type and the length of the array.
+#[doc(hidden)]
+#[inline(always)]
+pub const fn element_offset<B, T, const N: usize>(
+    _array: fn(*const B) -> *const [T; N],
+    idx: usize,
+) -> u32 {
+    assert!(idx < N, "register index out of bounds");
+    (idx * ::core::mem::size_of::<T>()) as u32
+}
+
+/// Get the address of a register in a peripheral instance.
+///
+/// # Examples
+/// ```rust,no_run
+/// # use imxrt_ral::addr_of; fn main() {
+/// let uart = imxrt_ral::lpuart::LPUART2::take().unwrap();
+///
+/// // Address of the UART's data register, for a DMA destination.
+/// let data: u32 = addr_of!(imxrt_ral::lpuart, uart, DATA);
+///
+/// // Address of an element in a register array.
+/// # let gpt = imxrt_ral::gpt::GPT1::take().unwrap();
+/// let ocr3: u32 = addr_of!(imxrt_ral::gpt, gpt, OCR[2]);
+/// # }
+/// ```
+///
+/// # Usage
+/// The arguments are the same as `read_reg!`'s:
+/// * the path to the peripheral module: `imxrt_ral::lpuart`,
+/// * a reference to the instance of that peripheral: 'uart' (anything which dereferences to
+///   `RegisterBlock`, such as `Instance`, `&Instance`, `&RegisterBlock`, or
+///   `*const RegisterBlock`),
+/// * the register: `DATA` (a field on the `RegisterBlock`), an element of a register array,
+///   like `OCR[n]`, or a register in a cluster, like `TCD[n].CSR`.
+///
+/// The macro evaluates to the `u32` address of the register. It never reads or writes the
+/// register. If you need the address in a const context, use
+/// [`offset_of!`](macro.offset_of.html) with the instance's `ADDRESS`.
+///
+/// # Safety
+/// This macro is safe to use with an `Instance`, `&Instance`, or `&RegisterBlock`. It requires an
+/// unsafe block when used with a `*const RegisterBlock`, since it dereferences the pointer.
+/// What you do with the address, like handing it to a DMA controller, may require its own
+/// synchronization.
+#[macro_export]
+macro_rules! addr_of {
+    ( $periph:path, $instance:expr, $reg:ident ) => {{
+        #[allow(unused_imports)]
+        use $periph::*;
+        (&(*$instance).$reg as *const _ as u32)
+    }};
+    ( $periph:path, $instance:expr, $reg:ident [ $idx:expr ] ) => {{
+        #[allow(unused_imports)]
+        use $periph::*;
+        (&(*$instance).$reg[$idx] as *const _ as u32)
+    }};
+    ( $periph:path, $instance:expr, $cluster:ident [ $idx:expr ] . $reg:ident ) => {{
+        #[allow(unused_imports)]
+        use $periph::*;
+        (&(*$instance).$cluster[$idx].$reg as *const _ as u32)
+    }};
 }
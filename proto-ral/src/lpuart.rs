@@ -49,6 +49,22 @@ pub struct RegisterBlock {
     /// LPUART Watermark Register
     pub WATER: RWRegister<u32>,
 }
+
+assert_layout!(RegisterBlock, size: 0x30, {
+    VERID: 0x00,
+    PARAM: 0x04,
+    GLOBAL: 0x08,
+    PINCFG: 0x0C,
+    BAUD: 0x10,
+    STAT: 0x14,
+    CTRL: 0x18,
+    DATA: 0x1C,
+    MATCH: 0x20,
+    MODIR: 0x24,
+    FIFO: 0x28,
+    WATER: 0x2C,
+});
+
 pub struct ResetValues {
     pub VERID: u32,
     pub PARAM: u32,
@@ -66,6 +82,7 @@ pub struct ResetValues {
 
 pub struct Instance {
     pub(crate) addr: u32,
+    pub(crate) reset: &'static ResetValues,
     pub(crate) _marker: PhantomData<*const RegisterBlock>,
 }
 
@@ -78,3 +95,34 @@ impl ::core::ops::Deref for Instance {
 }
 
 unsafe impl Send for Instance {}
+
+/// Restore every writable LPUART register to its reset value
+///
+/// This uses the LPUART's software reset, `GLOBAL[RST]`, which resets every
+/// register except `GLOBAL`. The reset holds until software clears `RST`, which
+/// also restores `GLOBAL`.
+pub fn reset_all(lpuart: &Instance) {
+    let reset = lpuart.reset;
+    lpuart.GLOBAL.write(reset.GLOBAL | GLOBAL::RST::mask);
+    lpuart.GLOBAL.write(reset.GLOBAL);
+}
+
+/// Returns every LPUART register that doesn't hold its reset value
+///
+/// This skips `DATA`, since reading `DATA` pops the receive FIFO.
+pub fn verify_reset(lpuart: &Instance) -> impl Iterator<Item = crate::ResetDifference> {
+    let reset = lpuart.reset;
+    crate::reset::differences([
+        ("VERID", reset.VERID, lpuart.VERID.read()),
+        ("PARAM", reset.PARAM, lpuart.PARAM.read()),
+        ("GLOBAL", reset.GLOBAL, lpuart.GLOBAL.read()),
+        ("PINCFG", reset.PINCFG, lpuart.PINCFG.read()),
+        ("BAUD", reset.BAUD, lpuart.BAUD.read()),
+        ("STAT", reset.STAT, lpuart.STAT.read()),
+        ("CTRL", reset.CTRL, lpuart.CTRL.read()),
+        ("MATCH", reset.MATCH, lpuart.MATCH.read()),
+        ("MODIR", reset.MODIR, lpuart.MODIR.read()),
+        ("FIFO", reset.FIFO, lpuart.FIFO.read()),
+        ("WATER", reset.WATER, lpuart.WATER.read()),
+    ])
+}
@@ -88,6 +88,8 @@ pub mod PARAM {
 /// LPUART Global Register
 pub mod GLOBAL {
 
+    builder!(RST);
+
     /// Software Reset
     pub mod RST {
         /// Offset (1 bits)
@@ -113,6 +115,8 @@ pub mod GLOBAL {
 /// LPUART Pin Configuration Register
 pub mod PINCFG {
 
+    builder!(TRGSEL);
+
     /// Trigger Select
     pub mod TRGSEL {
         /// Offset (0 bits)
@@ -144,6 +148,11 @@ pub mod PINCFG {
 /// LPUART Baud Rate Register
 pub mod BAUD {
 
+    builder!(
+        SBR, SBNS, RXEDGIE, LBKDIE, RESYNCDIS, BOTHEDGE, MATCFG, RIDMAE, RDMAE, TDMAE, OSR, M10,
+        MAEN2, MAEN1,
+    );
+
     /// Baud Rate Modulo Divisor.
     pub mod SBR {
         /// Offset (0 bits)
@@ -525,6 +534,11 @@ pub mod BAUD {
 /// LPUART Status Register
 pub mod STAT {
 
+    builder!(
+        MA2F, MA1F, PF, FE, NF, OR, IDLE, RDRF, TC, TDRE, RAF, LBKDE, BRK13, RWUID, RXINV, MSBF,
+        RXEDGIF, LBKDIF,
+    );
+
     /// Match 2 Flag
     pub mod MA2F {
         /// Offset (14 bits)
@@ -907,6 +921,11 @@ pub mod STAT {
 /// LPUART Control Register
 pub mod CTRL {
 
+    builder!(
+        PT, PE, ILT, WAKE, M, RSRC, DOZEEN, LOOPS, IDLECFG, M7, MA2IE, MA1IE, SBK, RWU, RE, TE,
+        ILIE, RIE, TCIE, TIE, PEIE, FEIE, NEIE, ORIE, TXINV, TXDIR, R9T8, R8T9,
+    );
+
     /// Parity Type
     pub mod PT {
         /// Offset (0 bits)
@@ -1503,6 +1522,11 @@ pub mod CTRL {
 /// LPUART Data Register
 pub mod DATA {
 
+    builder!(
+        R0T0, R1T1, R2T2, R3T3, R4T4, R5T5, R6T6, R7T7, R8T8, R9T9, IDLINE, RXEMPT, FRETSC,
+        PARITYE, NOISY,
+    );
+
     /// R0T0
     pub mod R0T0 {
         /// Offset (0 bits)
@@ -1752,6 +1776,8 @@ pub mod DATA {
 /// LPUART Match Address Register
 pub mod MATCH {
 
+    builder!(MA1, MA2);
+
     /// Match Address 1
     pub mod MA1 {
         /// Offset (0 bits)
@@ -1784,6 +1810,8 @@ pub mod MATCH {
 /// LPUART Modem IrDA Register
 pub mod MODIR {
 
+    builder!(TXCTSE, TXRTSE, TXRTSPOL, RXRTSE, TXCTSC, TXCTSSRC, RTSWATER, TNP, IREN);
+
     /// Transmitter clear-to-send enable
     pub mod TXCTSE {
         /// Offset (0 bits)
@@ -1976,6 +2004,11 @@ pub mod MODIR {
 /// LPUART FIFO Register
 pub mod FIFO {
 
+    builder!(
+        RXFIFOSIZE, RXFE, TXFIFOSIZE, TXFE, RXUFE, TXOFE, RXIDEN, RXFLUSH, TXFLUSH, RXUF, TXOF,
+        RXEMPT, TXEMPT,
+    );
+
     /// Receive FIFO Buffer Depth
     pub mod RXFIFOSIZE {
         /// Offset (0 bits)
@@ -2307,6 +2340,8 @@ pub mod FIFO {
 /// LPUART Watermark Register
 pub mod WATER {
 
+    builder!(TXWATER, TXCOUNT, RXWATER, RXCOUNT);
+
     /// Transmit Watermark
     pub mod TXWATER {
         /// Offset (0 bits)
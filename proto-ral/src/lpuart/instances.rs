@@ -21,8 +21,12 @@ pub mod LPUART1 {
 
     use super::Instance;
 
+    /// Base address of LPUART1
+    pub const ADDRESS: u32 = 0x40184000;
+
     const INSTANCE: Instance = Instance {
-        addr: 0x40184000,
+        addr: ADDRESS,
+        reset: &reset,
         _marker: ::core::marker::PhantomData,
     };
 
@@ -119,8 +123,12 @@ pub mod LPUART2 {
 
     use super::Instance;
 
+    /// Base address of LPUART2
+    pub const ADDRESS: u32 = 0x40188000;
+
     const INSTANCE: Instance = Instance {
-        addr: 0x40188000,
+        addr: ADDRESS,
+        reset: &reset,
         _marker: ::core::marker::PhantomData,
     };
 
@@ -217,8 +225,12 @@ pub mod LPUART3 {
 
     use super::Instance;
 
+    /// Base address of LPUART3
+    pub const ADDRESS: u32 = 0x4018c000;
+
     const INSTANCE: Instance = Instance {
-        addr: 0x4018c000,
+        addr: ADDRESS,
+        reset: &reset,
         _marker: ::core::marker::PhantomData,
     };
 
@@ -315,8 +327,12 @@ pub mod LPUART4 {
 
     use super::Instance;
 
+    /// Base address of LPUART4
+    pub const ADDRESS: u32 = 0x40190000;
+
     const INSTANCE: Instance = Instance {
-        addr: 0x40190000,
+        addr: ADDRESS,
+        reset: &reset,
         _marker: ::core::marker::PhantomData,
     };
 
@@ -413,8 +429,12 @@ pub mod LPUART5 {
 
     use super::Instance;
 
+    /// Base address of LPUART5
+    pub const ADDRESS: u32 = 0x40194000;
+
     const INSTANCE: Instance = Instance {
-        addr: 0x40194000,
+        addr: ADDRESS,
+        reset: &reset,
         _marker: ::core::marker::PhantomData,
     };
 
@@ -511,8 +531,12 @@ pub mod LPUART6 {
 
     use super::Instance;
 
+    /// Base address of LPUART6
+    pub const ADDRESS: u32 = 0x40198000;
+
     const INSTANCE: Instance = Instance {
-        addr: 0x40198000,
+        addr: ADDRESS,
+        reset: &reset,
         _marker: ::core::marker::PhantomData,
     };
 
@@ -609,8 +633,12 @@ pub mod LPUART7 {
 
     use super::Instance;
 
+    /// Base address of LPUART7
+    pub const ADDRESS: u32 = 0x4019c000;
+
     const INSTANCE: Instance = Instance {
-        addr: 0x4019c000,
+        addr: ADDRESS,
+        reset: &reset,
         _marker: ::core::marker::PhantomData,
     };
 
@@ -707,8 +735,12 @@ pub mod LPUART8 {
 
     use super::Instance;
 
+    /// Base address of LPUART8
+    pub const ADDRESS: u32 = 0x401a0000;
+
     const INSTANCE: Instance = Instance {
-        addr: 0x401a0000,
+        addr: ADDRESS,
+        reset: &reset,
         _marker: ::core::marker::PhantomData,
     };
 
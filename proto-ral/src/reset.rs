@@ -0,0 +1,38 @@
+//! Restoring peripherals to their reset values
+//!
+//! Each peripheral module provides
+//!
+//! - `reset_all(&instance)`, which restores every writable register to the
+//!   instance's `ResetValues`. It prefers the peripheral's own soft reset, if
+//!   it has one, and writes the rest in an order that won't glitch outputs or
+//!   raise interrupts.
+//! - `verify_reset(&instance)`, which reports every register that doesn't hold
+//!   its reset value.
+//!
+//! `verify_reset` skips registers that can't be meaningfully compared, like
+//! registers that reflect pad states, or registers where a read has side
+//! effects. Each peripheral documents what it skips.
+
+/// A register that doesn't hold its reset value
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResetDifference {
+    /// The register's name, like `"CR"` or `"OCR[1]"`
+    pub register: &'static str,
+    /// The documented reset value
+    pub expected: u32,
+    /// The value read from the register
+    pub actual: u32,
+}
+
+/// Returns the differences from a table of `(register, expected, actual)` values
+pub(crate) fn differences<const N: usize>(
+    registers: [(&'static str, u32, u32); N],
+) -> impl Iterator<Item = ResetDifference> {
+    IntoIterator::into_iter(registers)
+        .filter(|&(_, expected, actual)| expected != actual)
+        .map(|(register, expected, actual)| ResetDifference {
+            register,
+            expected,
+            actual,
+        })
+}
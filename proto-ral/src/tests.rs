@@ -0,0 +1,422 @@
+//! Macro tests
+//!
+//! The tests access a peripheral that lives in RAM. It has the shapes that the
+//! chip peripherals don't: a register cluster, and 8- and 16-bit registers.
+
+use crate::{addr_of, modify_reg, offset_of, read_reg, reset_reg, write_reg};
+
+/// A test peripheral
+mod periph {
+    use crate::RWRegister;
+
+    #[repr(C)]
+    pub struct RegisterBlock {
+        /// Control Register
+        pub CTRL: RWRegister<u32>,
+
+        /// Channels 0 and 1
+        pub CH: [CH::RegisterBlock; 2],
+
+        /// Data Register
+        pub DATA: RWRegister<u8>,
+
+        _reserved1: [u8; 1],
+
+        /// Halfword Register
+        pub HALF: RWRegister<u16>,
+    }
+
+    assert_layout!(RegisterBlock, size: 0x18, {
+        CTRL: 0x00,
+        CH: 0x04,
+        DATA: 0x14,
+        HALF: 0x16,
+    });
+
+    pub struct ResetValues {
+        pub CTRL: u32,
+        pub CH: [CH::ResetValues; 2],
+        pub DATA: u8,
+        pub HALF: u16,
+    }
+
+    /// Control Register
+    pub mod CTRL {
+
+        builder!(EN);
+
+        /// Enable
+        pub mod EN {
+            /// Offset (0 bits)
+            pub const offset: u32 = 0;
+            /// Mask (1 bit: 1 << 0)
+            pub const mask: u32 = 1 << offset;
+            /// Read-only values (empty)
+            pub mod R {}
+            /// Write-only values (empty)
+            pub mod W {}
+            /// Read-write values (empty)
+            pub mod RW {}
+        }
+    }
+
+    /// Data Register
+    pub mod DATA {
+
+        builder!(u8; LOW, HIGH);
+
+        /// Low nibble
+        pub mod LOW {
+            /// Offset (0 bits)
+            pub const offset: u32 = 0;
+            /// Mask (4 bits: 0xf << 0)
+            pub const mask: u8 = 0xf << offset;
+            /// Read-only values (empty)
+            pub mod R {}
+            /// Write-only values (empty)
+            pub mod W {}
+            /// Read-write values (empty)
+            pub mod RW {}
+        }
+
+        /// High nibble
+        pub mod HIGH {
+            /// Offset (4 bits)
+            pub const offset: u32 = 4;
+            /// Mask (4 bits: 0xf << 4)
+            pub const mask: u8 = 0xf << offset;
+            /// Read-only values (empty)
+            pub mod R {}
+            /// Write-only values (empty)
+            pub mod W {}
+            /// Read-write values
+            pub mod RW {
+                /// 0b1010: A marker value
+                pub const MARKER: u8 = 0b1010;
+            }
+        }
+    }
+
+    /// Halfword Register
+    pub mod HALF {
+
+        builder!(u16; VALUE, FLAG);
+
+        /// Value
+        pub mod VALUE {
+            /// Offset (0 bits)
+            pub const offset: u32 = 0;
+            /// Mask (15 bits: 0x7fff << 0)
+            pub const mask: u16 = 0x7fff << offset;
+            /// Read-only values (empty)
+            pub mod R {}
+            /// Write-only values (empty)
+            pub mod W {}
+            /// Read-write values (empty)
+            pub mod RW {}
+        }
+
+        /// Flag
+        pub mod FLAG {
+            /// Offset (15 bits)
+            pub const offset: u32 = 15;
+            /// Mask (1 bit: 1 << 15)
+            pub const mask: u16 = 1 << offset;
+            /// Read-only values (empty)
+            pub mod R {}
+            /// Write-only values (empty)
+            pub mod W {}
+            /// Read-write values
+            pub mod RW {
+                /// 0b0: Flag is clear
+                pub const CLEAR: u16 = 0b0;
+                /// 0b1: Flag is set
+                pub const SET: u16 = 0b1;
+            }
+        }
+    }
+
+    /// A channel
+    pub mod CH {
+        use crate::RWRegister;
+
+        #[repr(C)]
+        pub struct RegisterBlock {
+            /// Channel Configuration Register
+            pub CFG: RWRegister<u32>,
+
+            /// Channel Count Register
+            pub COUNT: RWRegister<u32>,
+        }
+
+        assert_layout!(RegisterBlock, size: 0x08, {
+            CFG: 0x00,
+            COUNT: 0x04,
+        });
+
+        pub struct ResetValues {
+            pub CFG: u32,
+            pub COUNT: u32,
+        }
+
+        /// Channel Configuration Register
+        pub mod CFG {
+
+            builder!(EN, MODE);
+
+            /// Enable the channel
+            pub mod EN {
+                /// Offset (0 bits)
+                pub const offset: u32 = 0;
+                /// Mask (1 bit: 1 << 0)
+                pub const mask: u32 = 1 << offset;
+                /// Read-only values (empty)
+                pub mod R {}
+                /// Write-only values (empty)
+                pub mod W {}
+                /// Read-write values (empty)
+                pub mod RW {}
+            }
+
+            /// Channel mode
+            pub mod MODE {
+                /// Offset (4 bits)
+                pub const offset: u32 = 4;
+                /// Mask (2 bits: 0b11 << 4)
+                pub const mask: u32 = 0b11 << offset;
+                /// Read-only values (empty)
+                pub mod R {}
+                /// Write-only values (empty)
+                pub mod W {}
+                /// Read-write values
+                pub mod RW {
+                    /// 0b01: Count once
+                    pub const ONE_SHOT: u32 = 0b01;
+                    /// 0b10: Count continuously
+                    pub const CONTINUOUS: u32 = 0b10;
+                }
+            }
+        }
+
+        /// Channel Count Register
+        pub mod COUNT {
+
+            builder!(VALUE);
+
+            /// Count value
+            pub mod VALUE {
+                /// Offset (0 bits)
+                pub const offset: u32 = 0;
+                /// Mask (16 bits: 0xffff << 0)
+                pub const mask: u32 = 0xffff << offset;
+                /// Read-only values (empty)
+                pub mod R {}
+                /// Write-only values (empty)
+                pub mod W {}
+                /// Read-write values (empty)
+                pub mod RW {}
+            }
+        }
+    }
+
+    /// The test instance
+    pub mod TEST {
+        use super::{ResetValues, CH};
+
+        /// Reset values for each field in TEST
+        pub const reset: ResetValues = ResetValues {
+            CTRL: 0x00000000,
+            CH: [
+                CH::ResetValues {
+                    CFG: 0x00000011,
+                    COUNT: 0x0000ffff,
+                },
+                CH::ResetValues {
+                    CFG: 0x00000020,
+                    COUNT: 0x00000100,
+                },
+            ],
+            DATA: 0x5a,
+            HALF: 0x8001,
+        };
+    }
+}
+
+use periph::RegisterBlock;
+
+/// Returns a test peripheral with every register cleared
+fn peripheral() -> RegisterBlock {
+    // Safety: registers are plain integers in UnsafeCells, and zero is valid.
+    unsafe { core::mem::zeroed() }
+}
+
+#[test]
+fn test_cluster_read_write() {
+    let block = peripheral();
+    let p = &block;
+
+    write_reg!(periph, p, CH[1].CFG, EN: 1, MODE: CONTINUOUS);
+    assert_eq!(read_reg!(periph, p, CH[1].CFG), 0x21);
+    assert_eq!(read_reg!(periph, p, CH[0].CFG), 0);
+    assert_eq!(read_reg!(periph, p, CTRL), 0);
+    assert_eq!(read_reg!(periph, p, CH[1].CFG, EN, MODE), (1, 0b10));
+    assert!(read_reg!(periph, p, CH[1].CFG, MODE == CONTINUOUS));
+
+    write_reg!(periph, p, CH[0].COUNT, 0x1234);
+    assert_eq!(read_reg!(periph, p, CH[0].COUNT, VALUE), 0x1234);
+    assert_eq!(read_reg!(periph, p, CH[1].COUNT), 0);
+}
+
+#[test]
+fn test_cluster_modify() {
+    let block = peripheral();
+    let p = &block;
+
+    write_reg!(periph, p, CH[1].CFG, EN: 1, MODE: CONTINUOUS);
+    modify_reg!(periph, p, CH[1].CFG, MODE: ONE_SHOT);
+    assert_eq!(read_reg!(periph, p, CH[1].CFG), 0x11);
+
+    modify_reg!(periph, p, CH[0].COUNT, |count| count + 5);
+    modify_reg!(periph, p, CH[0].COUNT, |count| count * 3);
+    assert_eq!(read_reg!(periph, p, CH[0].COUNT), 15);
+    assert_eq!(read_reg!(periph, p, CH[1].COUNT), 0);
+}
+
+#[test]
+fn test_cluster_reset() {
+    let block = peripheral();
+    let p = &block;
+
+    reset_reg!(periph, p, TEST, CH[0].COUNT);
+    reset_reg!(periph, p, TEST, CH[1].COUNT);
+    assert_eq!(read_reg!(periph, p, CH[0].COUNT), 0xffff);
+    assert_eq!(read_reg!(periph, p, CH[1].COUNT), 0x100);
+
+    // Resetting a field keeps the other fields
+    write_reg!(periph, p, CH[1].CFG, EN: 1, MODE: ONE_SHOT);
+    reset_reg!(periph, p, TEST, CH[1].CFG, MODE);
+    assert_eq!(read_reg!(periph, p, CH[1].CFG), 0x21);
+
+    reset_reg!(periph, p, TEST, CH[0].CFG);
+    assert_eq!(read_reg!(periph, p, CH[0].CFG), 0x11);
+
+    write_reg!(periph, p, CTRL, EN: 1);
+    reset_reg!(periph, p, TEST, CTRL);
+    assert_eq!(read_reg!(periph, p, CTRL), 0);
+}
+
+#[test]
+fn test_cluster_builder() {
+    use periph::{
+        CH::{CFG, COUNT},
+        CTRL,
+    };
+    const CONFIG: u32 = CFG::new().EN(1).MODE(CFG::MODE::RW::CONTINUOUS).bits();
+    assert_eq!(CONFIG, 0x21);
+    assert_eq!(COUNT::new().VALUE(0xffff).bits(), 0xffff);
+    assert_eq!(CTRL::new().EN(1).bits(), 1);
+
+    let block = peripheral();
+    let p = &block;
+    write_reg!(periph, p, CH[0].CFG, CONFIG);
+    assert_eq!(read_reg!(periph, p, CH[0].CFG, EN, MODE), (1, 0b10));
+}
+
+#[test]
+fn test_cluster_offsets() {
+    const CH1_COUNT: u32 = offset_of!(periph, CH[1].COUNT);
+    assert_eq!(offset_of!(periph, CTRL), 0x00);
+    assert_eq!(offset_of!(periph, CH[0].CFG), 0x04);
+    assert_eq!(offset_of!(periph, CH[0].COUNT), 0x08);
+    assert_eq!(offset_of!(periph, CH[1].CFG), 0x0C);
+    assert_eq!(CH1_COUNT, 0x10);
+
+    let block = peripheral();
+    let p = &block;
+    let base = p as *const RegisterBlock as u32;
+    assert_eq!(addr_of!(periph, p, CTRL), base);
+    assert_eq!(
+        addr_of!(periph, p, CH[1].COUNT),
+        base.wrapping_add(CH1_COUNT)
+    );
+}
+
+#[test]
+fn test_narrow_read_write() {
+    let block = peripheral();
+    let p = &block;
+
+    write_reg!(periph, p, DATA, LOW: 0x3, HIGH: MARKER);
+    let data: u8 = read_reg!(periph, p, DATA);
+    assert_eq!(data, 0xa3);
+    let (low, high): (u8, u8) = read_reg!(periph, p, DATA, LOW, HIGH);
+    assert_eq!((low, high), (0x3, 0xa));
+    assert!(read_reg!(periph, p, DATA, HIGH == MARKER));
+
+    write_reg!(periph, p, HALF, VALUE: 0x7fff, FLAG: SET);
+    let half: u16 = read_reg!(periph, p, HALF);
+    assert_eq!(half, 0xffff);
+    // Values that don't fit are masked
+    write_reg!(periph, p, HALF, VALUE: 0xffff);
+    assert_eq!(read_reg!(periph, p, HALF), 0x7fff);
+
+    // Neighboring registers are untouched
+    assert_eq!(read_reg!(periph, p, DATA), 0xa3);
+    assert_eq!(read_reg!(periph, p, CH[1].COUNT), 0);
+}
+
+#[test]
+fn test_narrow_modify() {
+    let block = peripheral();
+    let p = &block;
+
+    write_reg!(periph, p, DATA, 0xff);
+    modify_reg!(periph, p, DATA, LOW: 0);
+    assert_eq!(read_reg!(periph, p, DATA), 0xf0);
+    modify_reg!(periph, p, DATA, |data: u8| data.wrapping_add(0x20));
+    assert_eq!(read_reg!(periph, p, DATA), 0x10);
+
+    write_reg!(periph, p, HALF, VALUE: 0x1234);
+    modify_reg!(periph, p, HALF, FLAG: SET);
+    assert_eq!(read_reg!(periph, p, HALF), 0x9234);
+    modify_reg!(periph, p, HALF, |half: u16| half >> 1);
+    assert_eq!(read_reg!(periph, p, HALF), 0x491a);
+    modify_reg!(periph, p, HALF, FLAG: SET);
+    modify_reg!(periph, p, HALF, FLAG: CLEAR);
+    assert_eq!(read_reg!(periph, p, HALF), 0x491a);
+}
+
+#[test]
+fn test_narrow_reset() {
+    let block = peripheral();
+    let p = &block;
+
+    reset_reg!(periph, p, TEST, DATA);
+    assert_eq!(read_reg!(periph, p, DATA), 0x5a);
+    write_reg!(periph, p, DATA, 0xff);
+    reset_reg!(periph, p, TEST, DATA, HIGH);
+    assert_eq!(read_reg!(periph, p, DATA), 0x5f);
+
+    write_reg!(periph, p, HALF, VALUE: 0x10);
+    reset_reg!(periph, p, TEST, HALF, FLAG);
+    assert_eq!(read_reg!(periph, p, HALF), 0x8010);
+    reset_reg!(periph, p, TEST, HALF);
+    assert_eq!(read_reg!(periph, p, HALF), 0x8001);
+}
+
+#[test]
+fn test_narrow_builder_and_offsets() {
+    use periph::{DATA, HALF};
+    const DATA_VALUE: u8 = DATA::new().LOW(0x3).HIGH(DATA::HIGH::RW::MARKER).bits();
+    const HALF_VALUE: u16 = HALF::new().VALUE(0x10).FLAG(HALF::FLAG::RW::SET).bits();
+    assert_eq!(DATA_VALUE, 0xa3);
+    assert_eq!(HALF_VALUE, 0x8010);
+
+    assert_eq!(offset_of!(periph, DATA), 0x14);
+    assert_eq!(offset_of!(periph, HALF), 0x16);
+
+    let block = peripheral();
+    let p = &block;
+    let base = p as *const RegisterBlock as u32;
+    assert_eq!(addr_of!(periph, p, HALF), base.wrapping_add(0x16));
+}